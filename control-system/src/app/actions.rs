@@ -11,7 +11,7 @@ pub enum Action {
     NextPage,
     /// Go to previous page
     PrevPage,
-    /// Go to a specific page (1-4)
+    /// Go to a specific page (0-based index into `Page::ALL`)
     GoToPage(usize),
     /// Cycle focus between UI areas
     CycleFocus,
@@ -27,12 +27,52 @@ pub enum Action {
     ToggleHelp,
     /// Toggle animation pause
     TogglePause,
+    /// Mark the highlighted notification as read
+    MarkNotificationRead,
+    /// Mark all notifications as read
+    MarkAllNotificationsRead,
+    /// Cycle through tracked GitHub accounts
+    CycleAccount,
+    /// Narrow the received feed to the next event kind
+    CycleFeedFilter,
+    /// Hide events from the highlighted received event's repo
+    MuteRepo,
+    /// Hide events from the highlighted received event's actor
+    MuteActor,
+    /// Close the open detail view
+    Back,
+    /// Acknowledge new releases of watched repos
+    AcknowledgeReleases,
+    /// Start typing into the repositories filter
+    EditRepoFilter,
+    /// Append a character to the repositories filter
+    FilterInput(char),
+    /// Delete the last character of the repositories filter
+    FilterBackspace,
+    /// Stop typing, keeping the filter
+    FilterDone,
+    /// Stop typing and clear the filter
+    FilterClear,
+    /// Sort the repositories table by the next column
+    CycleRepoSort,
+    /// Flip the repositories table's sort direction
+    ReverseRepoSort,
+    /// Show/hide forks in the repositories table
+    ToggleForks,
+    /// Show/hide archived repos in the repositories table
+    ToggleArchived,
     /// Spotify: Toggle play/pause
     SpotifyToggle,
     /// Spotify: Next track
     SpotifyNext,
     /// Spotify: Previous track
     SpotifyPrev,
+    /// Spotify: Skip back 15 seconds
+    SpotifySeekBack,
+    /// Spotify: Skip forward 30 seconds
+    SpotifySeekForward,
+    /// Spotify: Cycle the sleep timer
+    SpotifySleepTimer,
     /// Reset Spotify configuration
     SpotifyReset,
     /// No action
@@ -50,6 +90,28 @@ impl Action {
             // Refresh
             KeyCode::Char('r') => Action::RefreshGithub,
 
+            // Notifications
+            KeyCode::Char('m') => Action::MarkNotificationRead,
+            KeyCode::Char('M') => Action::MarkAllNotificationsRead,
+
+            // Accounts
+            KeyCode::Char('a') => Action::CycleAccount,
+
+            // Watched releases
+            KeyCode::Char('n') => Action::AcknowledgeReleases,
+
+            // Received feed
+            KeyCode::Char('f') => Action::CycleFeedFilter,
+            KeyCode::Char('x') => Action::MuteRepo,
+            KeyCode::Char('X') => Action::MuteActor,
+
+            // Repositories table
+            KeyCode::Char('/') => Action::EditRepoFilter,
+            KeyCode::Char('s') => Action::CycleRepoSort,
+            KeyCode::Char('i') => Action::ReverseRepoSort,
+            KeyCode::Char('F') => Action::ToggleForks,
+            KeyCode::Char('A') => Action::ToggleArchived,
+
             // Page navigation
             KeyCode::Tab => {
                 if key.modifiers.contains(KeyModifiers::SHIFT) {
@@ -63,6 +125,7 @@ impl Action {
             KeyCode::Char('3') => Action::GoToPage(2),
             KeyCode::Char('4') => Action::GoToPage(3),
             KeyCode::Char('5') => Action::GoToPage(4),
+            KeyCode::Char('6') => Action::GoToPage(5),
 
             // Help
             KeyCode::Char('?') => Action::ToggleHelp,
@@ -79,6 +142,7 @@ impl Action {
 
             // Selection
             KeyCode::Enter => Action::SelectNext,
+            KeyCode::Esc => Action::Back,
 
             // Page up/down for faster scrolling
             KeyCode::PageUp => Action::ScrollUp,
@@ -88,6 +152,9 @@ impl Action {
             KeyCode::Char(' ') => Action::SpotifyToggle,
             KeyCode::Char('>') | KeyCode::Char('.') => Action::SpotifyNext,
             KeyCode::Char('<') | KeyCode::Char(',') => Action::SpotifyPrev,
+            KeyCode::Char('[') => Action::SpotifySeekBack,
+            KeyCode::Char(']') => Action::SpotifySeekForward,
+            KeyCode::Char('z') => Action::SpotifySleepTimer,
 
             // Reset Spotify (capital S)
             KeyCode::Char('S') => Action::SpotifyReset,
@@ -95,6 +162,18 @@ impl Action {
             _ => Action::None,
         }
     }
+
+    /// Convert a key event while the repositories filter is being typed
+    pub fn from_filter_key(key: KeyEvent) -> Self {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char(c) => Action::FilterInput(c),
+            KeyCode::Backspace => Action::FilterBackspace,
+            KeyCode::Enter => Action::FilterDone,
+            KeyCode::Esc => Action::FilterClear,
+            _ => Action::None,
+        }
+    }
 }
 
 /// Get keybind help text
@@ -102,7 +181,16 @@ pub fn keybind_help() -> Vec<(&'static str, &'static str)> {
     vec![
         ("q", "Quit"),
         ("r", "Refresh GitHub"),
-        ("1-4", "Switch pages"),
+        ("m/M", "Mark notification/all read"),
+        ("a", "Switch account"),
+        ("n", "Acknowledge new releases"),
+        ("Enter/Esc", "Open/close repo details"),
+        ("/", "Filter repos"),
+        ("s/i", "Sort repos/reverse"),
+        ("F/A", "Hide forks/archived"),
+        ("f", "Filter followed feed"),
+        ("x/X", "Mute repo/user in feed"),
+        ("1-6", "Switch pages"),
         ("Tab", "Cycle focus"),
         ("?/h", "Toggle help"),
         ("p", "Pause animations"),
@@ -112,5 +200,7 @@ pub fn keybind_help() -> Vec<(&'static str, &'static str)> {
         ("Space", "Play/Pause Spotify"),
         (">/.", "Next track"),
         ("<", "Prev track"),
+        ("[/]", "Skip -15s/+30s"),
+        ("z", "Sleep timer"),
     ]
 }
@@ -1,8 +1,12 @@
+use std::time::{Duration, Instant};
+
 use crate::app::logs::LogBuffer;
-use crate::github::GithubState;
+use crate::github::{EventKind, FeedSettings, GithubEvent, GithubRepo, GithubState, RepoQuery};
 use crate::news::NewsFeed;
 use crate::spotify::SpotifyState;
 use crate::system::stats::SystemState;
+use crate::system::Connectivity;
+use crate::ui::widgets::album_art::ArtMode;
 
 /// The current page being displayed
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,9 +17,21 @@ pub enum Page {
     Activity,
     Spotify,
     Settings,
+    Inbox,
 }
 
 impl Page {
+    /// All pages in tab order
+    pub const ALL: [Page; 6] = [
+        Page::Dashboard,
+        Page::Repositories,
+        Page::Activity,
+        Page::Spotify,
+        Page::Settings,
+        // Added after the others so their number keys stay the same
+        Page::Inbox,
+    ];
+
     pub fn title(&self) -> &'static str {
         match self {
             Page::Dashboard => "Dashboard",
@@ -23,36 +39,36 @@ impl Page {
             Page::Activity => "Activity Feed",
             Page::Spotify => "Spotify",
             Page::Settings => "Settings & Help",
+            Page::Inbox => "Inbox",
         }
     }
 
-    pub fn index(&self) -> usize {
+    /// Short label used in the header tabs
+    pub fn tab_label(&self) -> &'static str {
         match self {
-            Page::Dashboard => 0,
-            Page::Repositories => 1,
-            Page::Activity => 2,
-            Page::Spotify => 3,
-            Page::Settings => 4,
+            Page::Dashboard => "Dashboard",
+            Page::Repositories => "Repos",
+            Page::Activity => "Activity",
+            Page::Spotify => "Spotify",
+            Page::Settings => "Settings",
+            Page::Inbox => "Inbox",
         }
     }
 
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|p| p == self).unwrap_or(0)
+    }
+
     pub fn from_index(index: usize) -> Self {
-        match index {
-            0 => Page::Dashboard,
-            1 => Page::Repositories,
-            2 => Page::Activity,
-            3 => Page::Spotify,
-            4 => Page::Settings,
-            _ => Page::Dashboard,
-        }
+        Self::ALL.get(index).copied().unwrap_or_default()
     }
 
     pub fn next(&self) -> Self {
-        Self::from_index((self.index() + 1) % 5)
+        Self::from_index((self.index() + 1) % Self::ALL.len())
     }
 
     pub fn prev(&self) -> Self {
-        Self::from_index((self.index() + 4) % 5)
+        Self::from_index((self.index() + Self::ALL.len() - 1) % Self::ALL.len())
     }
 }
 
@@ -64,6 +80,24 @@ pub struct UiState {
     pub scroll_offset: usize,
     pub selected_index: usize,
     pub focus_area: FocusArea,
+    /// Built-in album art renderer, set when no image protocol was detected
+    pub art_fallback: Option<ArtMode>,
+    /// Index into `GithubState::accounts`; None shows every account combined
+    pub selected_account: Option<usize>,
+    /// Event kind the received feed is narrowed to (None shows all)
+    pub feed_kind: Option<EventKind>,
+    /// Repo (`owner/name`) whose detail page is open on the Repositories page
+    pub repo_detail: Option<String>,
+    /// Sort order, text filter and toggles of the repositories table
+    pub repo_query: RepoQuery,
+    /// Keys are typed into the repositories filter instead of acting
+    pub editing_repo_filter: bool,
+    /// Highlighted thread in the dashboard's notifications (clamped to the
+    /// list, see `move_notification_selection`)
+    pub notification_index: usize,
+    /// Highlighted row of the repositories table (clamped to the list, see
+    /// `move_repo_selection`)
+    pub repo_index: usize,
 }
 
 /// Which area of the UI has focus
@@ -151,6 +185,19 @@ impl FxState {
     }
 }
 
+/// How long a toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(6);
+
+/// Most toasts shown at once; older ones are dropped
+const MAX_TOASTS: usize = 4;
+
+/// A short-lived popup message
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub created: Instant,
+}
+
 /// Complete application state
 #[derive(Debug, Clone)]
 pub struct AppState {
@@ -162,6 +209,14 @@ pub struct AppState {
     pub fx: FxState,
     pub log_buffer: LogBuffer,
     pub running: bool,
+    /// Whether a GitHub token is configured (enables GraphQL-only features)
+    pub has_github_token: bool,
+    /// Popup messages, oldest first
+    pub toasts: Vec<Toast>,
+    /// Received-events feed settings, including mutes
+    pub feed: FeedSettings,
+    /// Whether the network is up; pollers pause while it isn't
+    pub connectivity: Connectivity,
 }
 
 impl Default for AppState {
@@ -175,6 +230,10 @@ impl Default for AppState {
             fx: FxState::default(),
             log_buffer: LogBuffer::new(),
             running: true,
+            has_github_token: false,
+            toasts: Vec::new(),
+            feed: FeedSettings::default(),
+            connectivity: Connectivity::default(),
         }
     }
 }
@@ -193,10 +252,105 @@ impl AppState {
         self.github.profile.is_some() || !self.github.repos.is_empty()
     }
 
+    /// Index of the highlighted notification thread on the dashboard,
+    /// clamped in case threads were marked read since it was moved
+    pub fn selected_notification_index(&self) -> usize {
+        let count = self.github.notifications.len();
+        self.ui.notification_index.min(count.saturating_sub(1))
+    }
+
+    /// Move the notification highlight by `delta` threads, stopping at
+    /// either end of the list
+    pub fn move_notification_selection(&mut self, delta: isize) {
+        let last = self.github.notifications.len().saturating_sub(1);
+        self.ui.notification_index = self
+            .selected_notification_index()
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+
+    /// Step the account selection: all accounts combined, then each one in
+    /// turn. Stays on the combined view with fewer than two accounts.
+    pub fn cycle_account(&mut self) {
+        let count = self.github.accounts.len();
+        self.ui.selected_account = match self.ui.selected_account {
+            _ if count < 2 => None,
+            None => Some(0),
+            Some(i) if i + 1 < count => Some(i + 1),
+            Some(_) => None,
+        };
+    }
+
+    /// Repos in the Repositories page's table, sorted and filtered
+    pub fn listed_repos(&self) -> Vec<&GithubRepo> {
+        self.ui
+            .repo_query
+            .apply(self.github.account_repos(self.ui.selected_account))
+    }
+
+    /// Index of the highlighted repo on the Repositories page, clamped in
+    /// case the list shrank since it was moved
+    pub fn selected_repo_index(&self) -> usize {
+        self.ui
+            .repo_index
+            .min(self.listed_repos().len().saturating_sub(1))
+    }
+
+    /// Move the repositories table highlight by `delta` rows, stopping at
+    /// either end of the list
+    pub fn move_repo_selection(&mut self, delta: isize) {
+        let last = self.listed_repos().len().saturating_sub(1);
+        self.ui.repo_index = self
+            .selected_repo_index()
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+
+    /// Received events left after the type filter and mutes
+    pub fn received_feed(&self) -> Vec<&GithubEvent> {
+        self.feed
+            .filter(&self.github.received_events, self.ui.feed_kind)
+    }
+
+    /// The highlighted received event on the Activity page (driven by the
+    /// scroll keys, clamped to the list)
+    pub fn selected_received_event(&self) -> Option<&GithubEvent> {
+        let events = self.received_feed();
+        let index = self.ui.scroll_offset.min(events.len().saturating_sub(1));
+        events.get(index).copied()
+    }
+
+    /// Show a toast, dropping the oldest if too many are queued
+    pub fn push_toast(&mut self, message: impl Into<String>) {
+        self.toasts.push(Toast {
+            message: message.into(),
+            created: Instant::now(),
+        });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Remove toasts that have been shown long enough
+    pub fn expire_toasts(&mut self) {
+        self.toasts.retain(|t| t.created.elapsed() < TOAST_DURATION);
+    }
+
     /// Get status message for the status bar
     pub fn status_message(&self) -> String {
         use crate::github::FetchStatus;
 
+        // Fetch errors from before the drop was detected would only repeat it
+        if !self.connectivity.online {
+            return match self.github.last_updated {
+                Some(last_updated) => format!(
+                    "Offline - data from {}",
+                    crate::util::time::format_relative(last_updated)
+                ),
+                None => "Offline - no data loaded".to_string(),
+            };
+        }
+
         match &self.github.status {
             FetchStatus::Idle => {
                 if let Some(last_updated) = self.github.last_updated {
@@ -2,6 +2,14 @@ use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::PathBuf;
+use tracing::warn;
+
+use crate::github::FeedSettings;
+use crate::spotify::SpotifySettings;
+use crate::store::{read_with_fallback, write_atomic};
+
+/// Public GitHub REST API
+pub const DEFAULT_GITHUB_API: &str = "https://api.github.com";
 
 /// Application configuration loaded from environment variables
 #[derive(Debug, Clone)]
@@ -10,12 +18,36 @@ pub struct Config {
     pub github_token: Option<String>,
     /// GitHub username (required)
     pub github_user: String,
+    /// Extra users and organizations to track alongside `github_user`
+    pub extra_accounts: Vec<String>,
+    /// REST API base URL (`https://HOST/api/v3` on GitHub Enterprise Server)
+    pub github_api_url: String,
+    /// GraphQL endpoint, derived from the API URL unless set explicitly
+    pub github_graphql_url: String,
+    /// PEM bundle of extra CA certificates to trust for GitHub requests
+    pub github_ca_bundle: Option<PathBuf>,
     /// Refresh interval in seconds (default: 60)
     pub refresh_secs: u64,
     /// Whether to reduce/disable animations
     pub reduced_motion: bool,
-    /// Path to cache file
-    pub cache_path: PathBuf,
+    /// Directory of the persistent store (GitHub cache, history, news, ...)
+    pub store_dir: PathBuf,
+    /// Spotify sleep timer and schedule settings (from settings.json)
+    pub spotify: SpotifySettings,
+    /// Repos to show on the Actions board ("owner/name" or a bare name for the
+    /// user's own repos). Empty means the most recently pushed non-fork repos.
+    pub actions_repos: Vec<String>,
+    /// Whether a newly broken default-branch build flashes the screen
+    pub actions_alert: bool,
+    /// Third-party repos ("owner/name") whose latest releases are watched
+    pub watch_releases: Vec<String>,
+    /// Received-events feed settings (from settings.json)
+    pub feed: FeedSettings,
+    /// Weigh the language chart by bytes of code (one request per repo,
+    /// conditional so unchanged repos are free) instead of counting repos
+    pub language_bytes: bool,
+    /// `host:port` opened to check the network is up before polling
+    pub connectivity_probe: String,
 }
 
 impl Config {
@@ -48,14 +80,81 @@ impl Config {
             .map(|v| v == "true" || v == "1")
             .unwrap_or(false);
 
-        let cache_path = Self::determine_cache_path();
+        let extra_accounts = env::var("CONTROL_SYSTEM_GITHUB_ACCOUNTS")
+            .map(|v| parse_accounts(&v, &github_user))
+            .unwrap_or_default();
+
+        let github_api_url = env::var("CONTROL_SYSTEM_GITHUB_API_URL")
+            .ok()
+            .map(|v| v.trim().trim_end_matches('/').to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_GITHUB_API.to_string());
+
+        let github_graphql_url = env::var("CONTROL_SYSTEM_GITHUB_GRAPHQL_URL")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| graphql_url_for(&github_api_url));
+
+        let github_ca_bundle = env::var("CONTROL_SYSTEM_GITHUB_CA_BUNDLE")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from);
+
+        let actions_repos = env::var("CONTROL_SYSTEM_ACTIONS_REPOS")
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let watch_releases = env::var("CONTROL_SYSTEM_WATCH_RELEASES")
+            .map(|v| parse_watchlist(&v))
+            .unwrap_or_default();
+
+        let actions_alert = env::var("CONTROL_SYSTEM_ACTIONS_ALERT")
+            .map(|v| v != "false" && v != "0")
+            .unwrap_or(true);
+
+        let language_bytes = env::var("CONTROL_SYSTEM_LANGUAGE_BYTES")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        let connectivity_probe = env::var("CONTROL_SYSTEM_CONNECTIVITY_PROBE")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| probe_for(&github_api_url));
+
+        let store_dir = Self::determine_store_dir();
+
+        let settings = AppSettings::load().unwrap_or_default();
+        let spotify = settings.spotify;
+
+        let mut feed = settings.received_feed;
+        if let Ok(v) = env::var("CONTROL_SYSTEM_RECEIVED_EVENTS") {
+            feed.enabled = v == "true" || v == "1";
+        }
 
         Ok(Config {
             github_token,
             github_user,
+            extra_accounts,
+            github_api_url,
+            github_graphql_url,
+            github_ca_bundle,
             refresh_secs,
             reduced_motion,
-            cache_path,
+            store_dir,
+            spotify,
+            actions_repos,
+            actions_alert,
+            watch_releases,
+            feed,
+            language_bytes,
+            connectivity_probe,
         })
     }
 
@@ -67,18 +166,18 @@ impl Config {
         }
     }
 
-    /// Determine the cache file path
-    fn determine_cache_path() -> PathBuf {
-        // Try ~/.config/control-system/cache.json first
+    /// Determine the store directory
+    fn determine_store_dir() -> PathBuf {
+        // Try ~/.config/control-system first
         if let Some(config_dir) = dirs::config_dir() {
             let app_dir = config_dir.join("control-system");
             if std::fs::create_dir_all(&app_dir).is_ok() {
-                return app_dir.join("cache.json");
+                return app_dir;
             }
         }
 
         // Fallback to current directory
-        PathBuf::from("./control-system-cache.json")
+        PathBuf::from("./control-system-data")
     }
 
     /// Check if we have a GitHub token configured
@@ -87,10 +186,69 @@ impl Config {
     }
 }
 
+/// GraphQL endpoint for a REST API base: GitHub Enterprise Server serves
+/// `/api/v3` and `/api/graphql` side by side, github.com uses `/graphql`
+fn graphql_url_for(api_url: &str) -> String {
+    match api_url.strip_suffix("/api/v3") {
+        Some(host) => format!("{}/api/graphql", host),
+        None => format!("{}/graphql", api_url),
+    }
+}
+
+/// `host:port` of the API server for the connectivity monitor to connect
+/// to; a hostname, so a broken DNS resolver counts as offline too
+fn probe_for(api_url: &str) -> String {
+    let address = url::Url::parse(api_url).ok().and_then(|url| {
+        let host = url.host_str()?.to_string();
+        Some(format!("{}:{}", host, url.port_or_known_default()?))
+    });
+    address.unwrap_or_else(|| {
+        warn!("Can't tell the host of {}, probing api.github.com", api_url);
+        "api.github.com:443".to_string()
+    })
+}
+
+/// Parse a comma-separated account list, dropping blanks, duplicates and
+/// the primary user
+fn parse_accounts(list: &str, primary: &str) -> Vec<String> {
+    let mut accounts: Vec<String> = Vec::new();
+    for login in list.split(',').map(str::trim) {
+        let duplicate = login.eq_ignore_ascii_case(primary)
+            || accounts.iter().any(|a| a.eq_ignore_ascii_case(login));
+        if !login.is_empty() && !duplicate {
+            accounts.push(login.to_string());
+        }
+    }
+    accounts
+}
+
+/// Parse a comma-separated list of `owner/name` repos, dropping blanks,
+/// duplicates and entries without an owner
+fn parse_watchlist(list: &str) -> Vec<String> {
+    let mut repos: Vec<String> = Vec::new();
+    for repo in list.split(',').map(str::trim) {
+        let valid = matches!(
+            repo.split('/').collect::<Vec<_>>()[..],
+            [owner, name] if !owner.is_empty() && !name.is_empty()
+        );
+        let duplicate = repos.iter().any(|r| r.eq_ignore_ascii_case(repo));
+        if valid && !duplicate {
+            repos.push(repo.to_string());
+        }
+    }
+    repos
+}
+
 /// Persistent app settings saved to disk
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppSettings {
     pub github_user: String,
+    /// Sleep timer mode and playback schedules
+    #[serde(default)]
+    pub spotify: SpotifySettings,
+    /// Feed of what followed users and watched repos are doing
+    #[serde(default)]
+    pub received_feed: FeedSettings,
 }
 
 impl AppSettings {
@@ -105,17 +263,34 @@ impl AppSettings {
         }
     }
 
-    /// Load settings from disk
+    /// Load settings from disk, or the previous save if the file is damaged
     pub fn load() -> Option<Self> {
-        let data = std::fs::read_to_string(Self::path()).ok()?;
-        serde_json::from_str(&data).ok()
+        read_with_fallback(&Self::path(), |data| Ok(serde_json::from_slice(data)?)).unwrap_or_else(
+            |e| {
+                warn!("Ignoring settings: {:#}", e);
+                None
+            },
+        )
     }
 
     /// Save settings to disk
     pub fn save(&self) -> Result<()> {
         let data = serde_json::to_string_pretty(self)?;
-        std::fs::write(Self::path(), data)?;
-        Ok(())
+        write_atomic(&Self::path(), data.as_bytes())
+    }
+
+    /// Load the settings, apply `change` and save them, one update at a time
+    /// so concurrent updates don't overwrite each other. Returns false
+    /// without saving if the settings couldn't be loaded.
+    pub fn update(change: impl FnOnce(&mut Self)) -> Result<bool> {
+        static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(mut settings) = Self::load() else {
+            return Ok(false);
+        };
+        change(&mut settings);
+        settings.save()?;
+        Ok(true)
     }
 }
 
@@ -124,8 +299,54 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_cache_path_fallback() {
-        let path = Config::determine_cache_path();
-        assert!(path.to_string_lossy().contains("cache.json"));
+    fn test_store_dir_fallback() {
+        let path = Config::determine_store_dir();
+        assert!(path.to_string_lossy().contains("control-system"));
+    }
+
+    #[test]
+    fn test_settings_without_spotify_section() {
+        let settings: AppSettings = serde_json::from_str(r#"{"github_user":"octocat"}"#).unwrap();
+        assert_eq!(settings.github_user, "octocat");
+        assert!(settings.spotify.schedules.is_empty());
+        assert_eq!(settings.spotify.fade_secs, 30);
+    }
+
+    #[test]
+    fn test_graphql_url_for() {
+        assert_eq!(
+            graphql_url_for(DEFAULT_GITHUB_API),
+            "https://api.github.com/graphql"
+        );
+        assert_eq!(
+            graphql_url_for("https://ghe.example.com/api/v3"),
+            "https://ghe.example.com/api/graphql"
+        );
+    }
+
+    #[test]
+    fn test_probe_for() {
+        assert_eq!(probe_for(DEFAULT_GITHUB_API), "api.github.com:443");
+        assert_eq!(
+            probe_for("https://ghe.example.com/api/v3"),
+            "ghe.example.com:443"
+        );
+        assert_eq!(probe_for("http://10.0.0.5:8080/api/v3"), "10.0.0.5:8080");
+    }
+
+    #[test]
+    fn test_parse_watchlist() {
+        assert_eq!(
+            parse_watchlist("tokio-rs/tokio, ratatui, ,Tokio-rs/Tokio,serde-rs/serde/"),
+            vec!["tokio-rs/tokio"]
+        );
+    }
+
+    #[test]
+    fn test_parse_accounts() {
+        assert_eq!(
+            parse_accounts(" acme, octocat ,Acme,,rust-lang", "Octocat"),
+            vec!["acme", "rust-lang"]
+        );
     }
 }
@@ -1,22 +1,55 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use tracing::{debug, info, warn};
 
-use super::models::{GithubEvent, GithubProfile, GithubRepo, GithubStats, RateLimit, GithubState};
+use super::models::{
+    ContributionCalendar, GithubAccount, GithubEvent, GithubProfile, GithubRepo, GithubStats, InboxItem,
+    Notification, RateLimit, GithubState, Validators, WatchedRelease, WorkflowRun,
+};
+use crate::store::{atomic::previous_path, Section, Store};
 use chrono::{DateTime, Utc};
 
+/// Cache format written by this build. Bump it when a change needs caches
+/// from older builds rewritten, and add the step to `migrate`.
+pub const CACHE_VERSION: u32 = 2;
+
 /// Serializable cache data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheData {
     pub profile: Option<GithubProfile>,
     pub repos: Vec<GithubRepo>,
+    #[serde(default)]
+    pub accounts: Vec<GithubAccount>,
     pub events: Vec<GithubEvent>,
     pub stats: GithubStats,
     pub rate_limit: RateLimit,
     pub last_updated: Option<DateTime<Utc>>,
     pub cache_version: u32,
+    #[serde(default)]
+    pub contributions: Option<ContributionCalendar>,
+    #[serde(default)]
+    pub inbox: Vec<InboxItem>,
+    #[serde(default)]
+    pub notifications: Vec<Notification>,
+    #[serde(default)]
+    pub workflow_runs: Vec<WorkflowRun>,
+    #[serde(default)]
+    pub announcements: Vec<GithubEvent>,
+    #[serde(default)]
+    pub received_events: Vec<GithubEvent>,
+    #[serde(default)]
+    pub language_bytes: BTreeMap<String, Vec<(String, u64)>>,
+    #[serde(default)]
+    pub watched_releases: Vec<WatchedRelease>,
+    #[serde(default)]
+    pub known_followers: BTreeSet<String>,
+    #[serde(default)]
+    pub known_stargazers: BTreeMap<String, BTreeSet<String>>,
+    #[serde(default)]
+    pub validators: BTreeMap<String, Validators>,
 }
 
 impl Default for CacheData {
@@ -24,11 +57,23 @@ impl Default for CacheData {
         Self {
             profile: None,
             repos: Vec::new(),
+            accounts: Vec::new(),
             events: Vec::new(),
             stats: GithubStats::default(),
             rate_limit: RateLimit::default(),
             last_updated: None,
-            cache_version: 1,
+            cache_version: CACHE_VERSION,
+            contributions: None,
+            inbox: Vec::new(),
+            notifications: Vec::new(),
+            workflow_runs: Vec::new(),
+            announcements: Vec::new(),
+            received_events: Vec::new(),
+            language_bytes: BTreeMap::new(),
+            watched_releases: Vec::new(),
+            known_followers: BTreeSet::new(),
+            known_stargazers: BTreeMap::new(),
+            validators: BTreeMap::new(),
         }
     }
 }
@@ -38,11 +83,23 @@ impl From<&GithubState> for CacheData {
         Self {
             profile: state.profile.clone(),
             repos: state.repos.clone(),
+            accounts: state.accounts.clone(),
             events: state.events.clone(),
             stats: state.stats.clone(),
             rate_limit: state.rate_limit.clone(),
             last_updated: state.last_updated,
-            cache_version: 1,
+            cache_version: CACHE_VERSION,
+            contributions: state.contributions.clone(),
+            inbox: state.inbox.clone(),
+            notifications: state.notifications.clone(),
+            workflow_runs: state.workflow_runs.clone(),
+            announcements: state.announcements.clone(),
+            received_events: state.received_events.clone(),
+            language_bytes: state.language_bytes.clone(),
+            watched_releases: state.watched_releases.clone(),
+            known_followers: state.known_followers.clone(),
+            known_stargazers: state.known_stargazers.clone(),
+            validators: state.validators.clone(),
         }
     }
 }
@@ -52,81 +109,149 @@ impl CacheData {
         GithubState {
             profile: self.profile.clone(),
             repos: self.repos.clone(),
+            accounts: self.accounts.clone(),
             events: self.events.clone(),
             stats: self.stats.clone(),
             rate_limit: self.rate_limit.clone(),
             last_updated: self.last_updated,
             status: super::models::FetchStatus::Idle,
+            contributions: self.contributions.clone(),
+            inbox: self.inbox.clone(),
+            notifications: self.notifications.clone(),
+            workflow_runs: self.workflow_runs.clone(),
+            announcements: self.announcements.clone(),
+            received_events: self.received_events.clone(),
+            language_bytes: self.language_bytes.clone(),
+            watched_releases: self.watched_releases.clone(),
+            known_followers: self.known_followers.clone(),
+            known_stargazers: self.known_stargazers.clone(),
+            validators: self.validators.clone(),
+            // History is loaded from its own store; scheduling starts fresh
+            ..Default::default()
         }
     }
 }
 
-/// GitHub data cache manager
+/// GitHub data cache manager (the store's GitHub section)
 pub struct GithubCache {
-    path: std::path::PathBuf,
+    store: Store,
 }
 
 impl GithubCache {
-    /// Create a new cache manager
-    pub fn new(path: impl AsRef<Path>) -> Self {
-        Self {
-            path: path.as_ref().to_path_buf(),
-        }
+    pub fn new(store: Store) -> Self {
+        Self { store }
     }
 
-    /// Load cached data from disk
+    /// Load cached data from disk, migrating caches written by older
+    /// versions. A damaged cache falls back to the copy kept by the previous
+    /// save; if that can't be read either, the file is moved aside (see
+    /// `backup`) rather than discarded.
     pub async fn load(&self) -> Result<Option<CacheData>> {
-        if !self.path.exists() {
-            debug!("Cache file does not exist: {:?}", self.path);
-            return Ok(None);
+        info!("Loading cache from {:?}", self.path());
+        match self.store.load_with(Section::Github, parse_cache).await {
+            Ok(Some(data)) => {
+                debug!("Loaded cache with {} repos", data.repos.len());
+                Ok(Some(data))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                warn!("Unreadable cache {:?}: {:#}", self.path(), e);
+                // Neither copy parsed, so the previous one goes aside too
+                let path = self.path();
+                for file in [path.clone(), previous_path(&path)] {
+                    if file.exists() {
+                        let backup = backup(&file).await?;
+                        warn!("Moved unreadable cache to {:?}", backup);
+                    }
+                }
+                Ok(None)
+            }
         }
-
-        info!("Loading cache from {:?}", self.path);
-        
-        let content = fs::read_to_string(&self.path).await?;
-        let data: CacheData = serde_json::from_str(&content)?;
-        
-        // Check cache version
-        if data.cache_version != 1 {
-            warn!("Cache version mismatch, ignoring cache");
-            return Ok(None);
-        }
-
-        debug!("Loaded cache with {} repos", data.repos.len());
-        Ok(Some(data))
     }
 
     /// Save data to cache
     pub async fn save(&self, state: &GithubState) -> Result<()> {
         let data = CacheData::from(state);
-        let content = serde_json::to_string_pretty(&data)?;
-        
-        // Ensure parent directory exists
-        if let Some(parent) = self.path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-
-        fs::write(&self.path, content).await?;
-        info!("Saved cache to {:?}", self.path);
-        
+        let content = serde_json::to_vec_pretty(&data)?;
+        self.store.write(Section::Github, &content).await?;
+        info!("Saved cache to {:?}", self.path());
         Ok(())
     }
 
+    fn path(&self) -> PathBuf {
+        self.store.path(Section::Github)
+    }
+
     /// Check if cache exists
     pub fn exists(&self) -> bool {
-        self.path.exists()
+        self.path().exists()
     }
 
     /// Delete cache file
     pub async fn clear(&self) -> Result<()> {
-        if self.path.exists() {
-            fs::remove_file(&self.path).await?;
-            info!("Cleared cache at {:?}", self.path);
+        let path = self.path();
+        for file in [previous_path(&path), path.clone()] {
+            if file.exists() {
+                fs::remove_file(&file).await?;
+            }
         }
+        info!("Cleared cache at {:?}", path);
         Ok(())
     }
 }
 
+/// Move a cache file aside as `<name>.<timestamp>.bak` next to it
+async fn backup(path: &Path) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stamp = Utc::now().format("%Y%m%dT%H%M%S");
+    let backup = path.with_file_name(format!("{}.{}.bak", name, stamp));
+    fs::rename(path, &backup).await?;
+    Ok(backup)
+}
+
+/// Parse cache JSON of any known version, migrating it to `CACHE_VERSION`
+fn parse_cache(content: &[u8]) -> Result<CacheData> {
+    let data: serde_json::Value = serde_json::from_slice(content)?;
+    let version = data["cache_version"]
+        .as_u64()
+        .context("missing cache_version")? as u32;
+    if version > CACHE_VERSION {
+        bail!(
+            "cache version {} is newer than this build's ({})",
+            version,
+            CACHE_VERSION
+        );
+    }
+    let data = if version < CACHE_VERSION {
+        info!("Migrating cache from version {} to {}", version, CACHE_VERSION);
+        migrate(data, version)?
+    } else {
+        data
+    };
+    Ok(serde_json::from_value(data)?)
+}
+
+/// Upgrade cache JSON from `version` one step at a time
+fn migrate(mut data: serde_json::Value, version: u32) -> Result<serde_json::Value> {
+    for from in version..CACHE_VERSION {
+        match from {
+            // Repos gained `archived` after ETags were being saved, so a 304
+            // would keep serving repo lists cached without it: refetch once
+            1 => {
+                if let Some(fields) = data.as_object_mut() {
+                    fields.remove("validators");
+                }
+            }
+            _ => bail!("no migration from cache version {}", from),
+        }
+        data["cache_version"] = (from + 1).into();
+    }
+    Ok(data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,8 +260,7 @@ mod tests {
     #[tokio::test]
     async fn test_cache_roundtrip() {
         let dir = tempdir().unwrap();
-        let cache_path = dir.path().join("test-cache.json");
-        let cache = GithubCache::new(&cache_path);
+        let cache = GithubCache::new(Store::new(dir.path()));
 
         let state = GithubState {
             profile: Some(GithubProfile {
@@ -157,6 +281,8 @@ mod tests {
                 pushed_at: None,
                 open_issues_count: 5,
                 fork: false,
+                default_branch: Some("main".to_string()),
+                archived: false,
             }],
             events: Vec::new(),
             stats: GithubStats {
@@ -168,6 +294,18 @@ mod tests {
             rate_limit: RateLimit::default(),
             last_updated: Some(Utc::now()),
             status: super::super::models::FetchStatus::Success,
+            contributions: Some(ContributionCalendar {
+                total: 3,
+                days: vec![super::super::models::ContributionDay {
+                    date: chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+                    count: 3,
+                    level: 2,
+                }],
+            }),
+            inbox: Vec::new(),
+            notifications: Vec::new(),
+            workflow_runs: Vec::new(),
+            ..Default::default()
         };
 
         // Save
@@ -180,5 +318,101 @@ mod tests {
         assert_eq!(loaded.repos.len(), 1);
         assert_eq!(loaded.repos[0].name, "test-repo");
         assert_eq!(loaded.stats.total_stars, 42);
+        assert_eq!(loaded.contributions.unwrap().days[0].count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_load_v1_fixtures() {
+        let dir = tempdir().unwrap();
+        let cache = GithubCache::new(Store::new(dir.path()));
+        let cache_path = cache.path();
+
+        // As first written, before any optional fields existed
+        std::fs::write(&cache_path, include_str!("../../tests/fixtures/cache_v1.json")).unwrap();
+        let loaded = cache.load().await.unwrap().unwrap();
+        assert_eq!(loaded.cache_version, CACHE_VERSION);
+        assert_eq!(loaded.profile.unwrap().login, "octocat");
+        assert_eq!(loaded.repos[0].stargazers_count, 2600);
+        assert!(!loaded.repos[0].archived);
+        assert_eq!(loaded.events.len(), 1);
+
+        // A later v1 with saved validators: they are dropped so repos are refetched
+        std::fs::write(
+            &cache_path,
+            include_str!("../../tests/fixtures/cache_v1_validators.json"),
+        )
+        .unwrap();
+        let loaded = cache.load().await.unwrap().unwrap();
+        assert!(loaded.validators.is_empty());
+        assert!(loaded.known_followers.contains("alice"));
+        assert_eq!(loaded.repos[0].default_branch.as_deref(), Some("master"));
+    }
+
+    #[tokio::test]
+    async fn test_truncated_cache_uses_previous_save() {
+        let dir = tempdir().unwrap();
+        let cache = GithubCache::new(Store::new(dir.path()));
+
+        let mut state = GithubState {
+            stats: GithubStats {
+                total_stars: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        cache.save(&state).await.unwrap();
+        state.stats.total_stars = 2;
+        cache.save(&state).await.unwrap();
+
+        // Power cut halfway through writing the second save
+        let content = std::fs::read(cache.path()).unwrap();
+        std::fs::write(cache.path(), &content[..content.len() / 2]).unwrap();
+        let loaded = cache.load().await.unwrap().unwrap();
+        assert_eq!(loaded.stats.total_stars, 1);
+
+        cache.clear().await.unwrap();
+        assert!(cache.load().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_cache_is_backed_up() {
+        let dir = tempdir().unwrap();
+        let cache = GithubCache::new(Store::new(dir.path()));
+        let cache_path = cache.path();
+
+        // Corrupt, unversioned, and written by a newer build
+        for content in [
+            "{ not json",
+            r#"{"repos": []}"#,
+            r#"{"cache_version": 99, "repos": []}"#,
+        ] {
+            std::fs::write(&cache_path, content).unwrap();
+            assert!(cache.load().await.unwrap().is_none());
+            assert!(!cache.exists());
+
+            let backups: Vec<_> = std::fs::read_dir(dir.path())
+                .unwrap()
+                .flatten()
+                .map(|e| e.path())
+                .collect();
+            assert_eq!(backups.len(), 1);
+            assert!(backups[0].to_string_lossy().ends_with(".bak"));
+            assert_eq!(std::fs::read_to_string(&backups[0]).unwrap(), content);
+            std::fs::remove_file(&backups[0]).unwrap();
+        }
+
+        // Both the cache and its previous copy are damaged
+        std::fs::write(&cache_path, "{ not json").unwrap();
+        std::fs::write(previous_path(&cache_path), "[").unwrap();
+        assert!(cache.load().await.unwrap().is_none());
+        let mut backups: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        backups.sort();
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].starts_with("cache.json.2"));
+        assert!(backups[1].starts_with("cache.json.prev.2"));
     }
 }
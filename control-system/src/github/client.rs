@@ -1,34 +1,150 @@
 use anyhow::Result;
 use chrono::Utc;
+use http_body_util::{BodyExt, Full};
+use octocrab::service::middleware::{
+    auth_header::AuthHeaderLayer, base_uri::BaseUriLayer, extra_headers::ExtraHeadersLayer,
+};
 use octocrab::Octocrab;
+use reqwest::header::USER_AGENT;
+use reqwest::header::{
+    HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::StatusCode;
+use rustls::pki_types::{pem::PemObject, CertificateDer};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
 use crate::config::Config;
 
 use super::models::{
-    FetchStatus, GithubEvent, GithubEventType, GithubProfile, GithubRepo, GithubState, RateLimit,
+    AccountKind, CiStatus, CommitSummary, ContributionCalendar, ContributionDay, EventPayload,
+    FetchStatus, GithubAccount, GithubEvent, GithubEventType, GithubProfile, GithubRepo,
+    GithubState, GithubStats, InboxItem, InboxKind, Notification, NotificationReason, PollHints,
+    RateLimit, ReleaseSummary, RepoDetail, RepoTraffic, ReviewState, RunState, TrafficDay,
+    Validators, WatchedRelease, WorkflowRun,
 };
 
+/// Repos requested per page
+const REPOS_PER_PAGE: usize = 100;
+
+/// Pages of repos fetched per account (3000 repos), so a huge organization
+/// can't use up the rate limit on its own
+const REPOS_MAX_PAGES: usize = 30;
+
+/// GraphQL query for the last year of the contribution calendar
+const CONTRIBUTIONS_QUERY: &str = r#"
+query($login: String!) {
+  user(login: $login) {
+    contributionsCollection {
+      contributionCalendar {
+        totalContributions
+        weeks {
+          contributionDays { date contributionCount contributionLevel }
+        }
+      }
+    }
+  }
+}"#;
+
+/// GraphQL query for open PRs and issues that need the user's attention.
+/// Each search alias maps to one [`InboxKind`].
+const INBOX_QUERY: &str = r#"
+query($authored: String!, $review: String!, $assigned: String!) {
+  authored: search(query: $authored, type: ISSUE, first: 30) { nodes { ...pr } }
+  review: search(query: $review, type: ISSUE, first: 30) { nodes { ...pr } }
+  assigned: search(query: $assigned, type: ISSUE, first: 30) { nodes { ...issue } }
+}
+fragment pr on PullRequest {
+  number title url createdAt updatedAt isDraft reviewDecision
+  author { login }
+  repository { nameWithOwner }
+  labels(first: 5) { nodes { name } }
+  commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+}
+fragment issue on Issue {
+  number title url createdAt updatedAt
+  author { login }
+  repository { nameWithOwner }
+  labels(first: 5) { nodes { name } }
+}"#;
+
+/// Repos whose language bytes are tracked (one conditional request each)
+const LANGUAGE_BYTES_MAX_REPOS: usize = 30;
+
+/// Commits listed on the repository detail page
+const REPO_DETAIL_COMMITS: usize = 10;
+
+/// Commit message headlines kept per push event
+const MAX_COMMIT_MESSAGES: usize = 2;
+
+/// Stargazer/follower announcements kept for the feed
+const MAX_ANNOUNCEMENTS: usize = 50;
+
+/// The API asked us to slow down: a secondary rate limit (429, or a 403
+/// with `Retry-After` or saying so, while quota remains) or a server error
+#[derive(Debug, thiserror::Error)]
+#[error("GitHub asked to slow down ({status})")]
+pub struct Throttled {
+    pub status: StatusCode,
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Result of a conditional GET
+struct ConditionalResponse {
+    /// None on 304 Not Modified
+    body: Option<serde_json::Value>,
+    /// `X-Poll-Interval`, sent by the events endpoints
+    poll_interval_secs: Option<u64>,
+}
+
 /// GitHub API client wrapper
 pub struct GithubClient {
     client: Octocrab,
+    /// Client for the GraphQL endpoint, posted to at `graphql_path`
+    graphql: Octocrab,
+    graphql_path: String,
     username: String,
+    /// GraphQL requires authentication, so some data is only fetched with a token
+    has_token: bool,
+    /// Users and organizations tracked alongside `username`
+    extra_accounts: Vec<String>,
+    /// Whether to fetch the received-events feed
+    received_feed: bool,
+    /// Whether to fetch per-repo language byte counts
+    language_bytes: bool,
+    /// Repos configured for the Actions board
+    actions_repos: Vec<String>,
+    /// Third-party repos whose releases are watched
+    watch_releases: Vec<String>,
 }
 
 impl GithubClient {
-    /// Create a new GitHub client
+    /// Create a new GitHub client for the configured API (github.com or a
+    /// GitHub Enterprise Server instance)
     pub fn new(config: &Config) -> Result<Self> {
-        let builder = if let Some(ref token) = config.github_token {
-            Octocrab::builder().personal_token(token.clone())
-        } else {
-            Octocrab::builder()
-        };
-
-        let client = builder.build()?;
+        let client = build_octocrab(config, &config.github_api_url)?;
+        // GraphQL gets its own client: on Enterprise it lives outside the
+        // REST base path, which octocrab would otherwise prepend
+        let graphql = build_octocrab(config, &config.github_graphql_url)?;
+        let graphql_path = config
+            .github_graphql_url
+            .parse::<http::Uri>()?
+            .path()
+            .to_string();
 
         Ok(Self {
             client,
+            graphql,
+            graphql_path,
             username: config.github_user.clone(),
+            extra_accounts: config.extra_accounts.clone(),
+            received_feed: config.feed.enabled,
+            language_bytes: config.language_bytes,
+            has_token: config.has_token(),
+            actions_repos: config.actions_repos.clone(),
+            watch_releases: config.watch_releases.clone(),
         })
     }
 
@@ -36,51 +152,167 @@ impl GithubClient {
     pub async fn fetch_all(&self, current_state: &GithubState) -> GithubState {
         let mut state = GithubState {
             status: FetchStatus::Fetching,
+            poll_hints: PollHints::default(),
             ..current_state.clone()
         };
 
         info!("Fetching GitHub data for user: {}", self.username);
 
-        // Fetch profile (non-fatal: continue even if this fails)
+        // Fetch each account's profile and repos (non-fatal: continue even if one fails)
         let mut errors: Vec<String> = Vec::new();
-        match self.fetch_profile().await {
-            Ok(profile) => {
-                debug!("Fetched profile for {}", profile.login);
-                state.profile = Some(profile);
-            }
-            Err(e) => {
-                error!("Failed to fetch profile: {}", e);
-                errors.push(format!("profile: {}", e));
-            }
-        }
-
-        // Fetch repositories
-        match self.fetch_repos().await {
-            Ok(repos) => {
-                debug!("Fetched {} repositories", repos.len());
-                state.repos = repos;
-            }
-            Err(e) => {
-                error!("Failed to fetch repos: {}", e);
-                errors.push(format!("repos: {}", e));
-            }
+        let mut accounts = Vec::new();
+        for login in self.account_logins() {
+            let previous = current_state
+                .accounts
+                .iter()
+                .find(|a| a.login.eq_ignore_ascii_case(login))
+                .cloned()
+                .or_else(|| {
+                    // Caches from before multi-account support only hold the primary user
+                    (login == self.username).then(|| GithubAccount {
+                        login: login.to_string(),
+                        kind: AccountKind::User,
+                        profile: current_state.profile.clone(),
+                        repos: current_state.repos.clone(),
+                        repos_truncated: false,
+                        stats: current_state.stats.clone(),
+                    })
+                });
+            let account = self
+                .fetch_account(login, previous, &mut state, &mut errors)
+                .await;
+            accounts.push(account);
         }
+        state.profile = accounts.first().and_then(|a| a.profile.clone());
+        state.repos = accounts.iter().flat_map(|a| a.repos.clone()).collect();
+        state.accounts = accounts;
 
         // Fetch events
         let existing_event_ids: std::collections::HashSet<_> =
             current_state.events.iter().map(|e| e.id.clone()).collect();
 
-        match self.fetch_events(&existing_event_ids).await {
-            Ok(events) => {
-                debug!("Fetched {} events", events.len());
-                state.events = events;
+        match self
+            .fetch_events(&existing_event_ids, &mut state.validators)
+            .await
+        {
+            Ok((events, poll_interval)) => {
+                state.poll_hints.poll_interval_secs = poll_interval;
+                match events {
+                    Some(events) => {
+                        debug!("Fetched {} events", events.len());
+                        state.events = events;
+                    }
+                    None => {
+                        debug!("Events unchanged");
+                        for event in &mut state.events {
+                            event.is_new = false;
+                        }
+                    }
+                }
             }
             Err(e) => {
                 warn!("Failed to fetch events: {}", e);
+                note_throttle(&mut state.poll_hints, &e);
                 // Don't fail completely for events
             }
         }
 
+        // Activity of followed users and watched repos (optional)
+        if self.received_feed {
+            let existing_ids: std::collections::HashSet<_> = current_state
+                .received_events
+                .iter()
+                .map(|e| e.id.clone())
+                .collect();
+            let url = format!("/users/{}/received_events?per_page=50", self.username);
+            match self
+                .fetch_event_list(&url, &existing_ids, &mut state.validators)
+                .await
+            {
+                Ok((events, poll_interval)) => {
+                    state.poll_hints.poll_interval_secs =
+                        state.poll_hints.poll_interval_secs.max(poll_interval);
+                    match events {
+                        Some(events) => {
+                            debug!("Fetched {} received events", events.len());
+                            state.received_events = events;
+                        }
+                        None => {
+                            for event in &mut state.received_events {
+                                event.is_new = false;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to fetch received events: {}", e);
+                    note_throttle(&mut state.poll_hints, &e);
+                }
+            }
+        }
+
+        // Bytes per language for the language chart (optional)
+        if self.language_bytes {
+            self.fetch_language_bytes(&mut state).await;
+        } else {
+            state.language_bytes.clear();
+        }
+
+        // Latest releases of watched upstream repos
+        self.fetch_watched_releases(&mut state).await;
+
+        // Announce new stargazers and followers
+        self.detect_new_fans(current_state, &mut state).await;
+
+        // Fetch contribution calendar (GraphQL, token only)
+        if self.has_token {
+            match self.fetch_contributions().await {
+                Ok(calendar) => {
+                    debug!("Fetched {} contribution days", calendar.days.len());
+                    state.contributions = Some(calendar);
+                }
+                Err(e) => {
+                    // Keep the cached calendar
+                    warn!("Failed to fetch contributions: {}", e);
+                }
+            }
+        }
+
+        // Fetch PRs and issues for the inbox (GraphQL, token only)
+        if self.has_token {
+            match self.fetch_inbox().await {
+                Ok(inbox) => {
+                    debug!("Fetched {} inbox items", inbox.len());
+                    state.inbox = inbox;
+                }
+                Err(e) => {
+                    warn!("Failed to fetch inbox: {}", e);
+                }
+            }
+        }
+
+        // Fetch unread notifications (token only)
+        if self.has_token {
+            match self.fetch_notifications().await {
+                Ok(notifications) => {
+                    debug!("Fetched {} unread notifications", notifications.len());
+                    state.notifications = notifications;
+                }
+                Err(e) => {
+                    warn!("Failed to fetch notifications: {}", e);
+                    note_throttle(&mut state.poll_hints, &e);
+                }
+            }
+        }
+
+        // Fetch Actions runs (token only; one conditional request per repo,
+        // free when nothing ran since the last refresh)
+        if self.has_token {
+            let repos = self.actions_repos(&state.repos);
+            self.fetch_workflow_runs(&repos, &mut state).await;
+            debug!("Fetched {} workflow runs", state.workflow_runs.len());
+        }
+
         // Fetch rate limit
         match self.fetch_rate_limit().await {
             Ok(rate_limit) => {
@@ -121,117 +353,733 @@ impl GithubClient {
         state
     }
 
-    /// Fetch user profile
-    async fn fetch_profile(&self) -> Result<GithubProfile> {
-        let user = self.client.users(&self.username).profile().await?;
-
-        Ok(GithubProfile {
-            login: user.login,
-            name: user.name,
-            avatar_url: user.avatar_url.to_string(),
-            bio: user.bio,
-            public_repos: user.public_repos as u32,
-            public_gists: user.public_gists as u32,
-            followers: user.followers as u32,
-            following: user.following as u32,
-            created_at: Some(user.created_at),
+    /// GET a JSON endpoint conditionally, sending the validators saved from
+    /// its last response. Returns None on 304 Not Modified, which doesn't
+    /// count against the rate limit; the caller keeps what it already has.
+    async fn get_conditional(
+        &self,
+        url: &str,
+        validators: &mut BTreeMap<String, Validators>,
+    ) -> Result<ConditionalResponse> {
+        let mut headers = HeaderMap::new();
+        if let Some(saved) = validators.get(url) {
+            if let Some(value) = saved
+                .etag
+                .as_deref()
+                .and_then(|v| HeaderValue::from_str(v).ok())
+            {
+                headers.insert(IF_NONE_MATCH, value);
+            }
+            if let Some(value) = saved
+                .last_modified
+                .as_deref()
+                .and_then(|v| HeaderValue::from_str(v).ok())
+            {
+                headers.insert(IF_MODIFIED_SINCE, value);
+            }
+        }
+
+        let response = self.client._get_with_headers(url, Some(headers)).await?;
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let poll_interval_secs = header("x-poll-interval").and_then(|v| v.parse().ok());
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(ConditionalResponse {
+                body: None,
+                poll_interval_secs,
+            });
+        }
+        let quota_left = header("x-ratelimit-remaining").is_none_or(|v| v != "0");
+        let retry_after_secs = header("retry-after").and_then(|v| v.trim().parse().ok());
+        let throttled = Throttled {
+            status,
+            retry_after_secs,
+        };
+        let response = match status {
+            StatusCode::TOO_MANY_REQUESTS if quota_left => return Err(throttled.into()),
+            StatusCode::FORBIDDEN if quota_left && retry_after_secs.is_some() => {
+                return Err(throttled.into())
+            }
+            StatusCode::FORBIDDEN if quota_left => {
+                // Most 403s are missing permissions (a fine-grained token
+                // without access, SAML SSO); only the message tells a
+                // secondary rate limit apart
+                let (parts, body) = response.into_parts();
+                let body = body.collect().await?.to_bytes();
+                if String::from_utf8_lossy(&body).contains("secondary rate limit") {
+                    return Err(throttled.into());
+                }
+                let body = Full::new(body).map_err(|e| match e {}).boxed();
+                http::Response::from_parts(parts, body)
+            }
+            status if status.is_server_error() => return Err(throttled.into()),
+            _ => response,
+        };
+        let response = octocrab::map_github_error(response).await?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v: &HeaderValue| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let fresh = Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        if fresh == Validators::default() {
+            validators.remove(url);
+        } else {
+            validators.insert(url.to_string(), fresh);
+        }
+
+        let body = self.client.body_to_string(response).await?;
+        Ok(ConditionalResponse {
+            body: Some(serde_json::from_str(&body)?),
+            poll_interval_secs,
         })
     }
 
-    /// Fetch user repositories
-    async fn fetch_repos(&self) -> Result<Vec<GithubRepo>> {
+    /// Primary user first, then the extra tracked accounts
+    fn account_logins(&self) -> Vec<&str> {
+        std::iter::once(self.username.as_str())
+            .chain(self.extra_accounts.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Fetch one account's profile and repos, keeping what we had for
+    /// anything unchanged or failing
+    async fn fetch_account(
+        &self,
+        login: &str,
+        previous: Option<GithubAccount>,
+        state: &mut GithubState,
+        errors: &mut Vec<String>,
+    ) -> GithubAccount {
+        let mut account = previous.unwrap_or_else(|| GithubAccount {
+            login: login.to_string(),
+            kind: AccountKind::User,
+            profile: None,
+            repos: Vec::new(),
+            repos_truncated: false,
+            stats: GithubStats::default(),
+        });
+        // Errors for the primary user keep their old prefix
+        let prefix = if login == self.username {
+            String::new()
+        } else {
+            format!("{} ", login)
+        };
+
+        if account.profile.is_none() {
+            // Nothing to fall back on, so don't let the server say 304
+            state.validators.remove(&format!("/users/{}", login));
+        }
+        match self.fetch_profile(login, &mut state.validators).await {
+            Ok(Some((profile, kind))) => {
+                debug!("Fetched profile for {}", profile.login);
+                account.profile = Some(profile);
+                account.kind = kind;
+            }
+            Ok(None) => debug!("Profile of {} unchanged", login),
+            Err(e) => {
+                error!("Failed to fetch profile of {}: {}", login, e);
+                note_throttle(&mut state.poll_hints, &e);
+                errors.push(format!("{}profile: {}", prefix, e));
+            }
+        }
+
+        match self
+            .fetch_repos(login, account.kind, &account.repos, &mut state.validators)
+            .await
+        {
+            Ok((repos, truncated)) => {
+                debug!("Fetched {} repositories for {}", repos.len(), login);
+                if truncated {
+                    warn!(
+                        "Stopped listing repositories of {} at the page limit ({})",
+                        login,
+                        repos.len()
+                    );
+                }
+                account.repos = repos;
+                account.repos_truncated = truncated;
+            }
+            Err(e) => {
+                error!("Failed to fetch repos of {}: {}", login, e);
+                note_throttle(&mut state.poll_hints, &e);
+                errors.push(format!("{}repos: {}", prefix, e));
+            }
+        }
+        account
+    }
+
+    /// Fetch a user or organization profile (None if unchanged since the last fetch)
+    async fn fetch_profile(
+        &self,
+        login: &str,
+        validators: &mut BTreeMap<String, Validators>,
+    ) -> Result<Option<(GithubProfile, AccountKind)>> {
+        let url = format!("/users/{}", login);
+        let Some(body) = self.get_conditional(&url, validators).await?.body else {
+            return Ok(None);
+        };
+        parse_profile(&body)
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("unexpected profile response for {}", login))
+    }
+
+    /// Fetch the repositories of an account, page by page until a short one
+    /// or `REPOS_MAX_PAGES` (true when cut short there). Pages that come back
+    /// 304 reuse the same slice of `previous`, which was built from the same
+    /// pages.
+    async fn fetch_repos(
+        &self,
+        login: &str,
+        kind: AccountKind,
+        previous: &[GithubRepo],
+        validators: &mut BTreeMap<String, Validators>,
+    ) -> Result<(Vec<GithubRepo>, bool)> {
+        // The org endpoint also lists private repos the token can see
+        let base = match kind {
+            AccountKind::User => format!("/users/{}/repos", login),
+            AccountKind::Organization => format!("/orgs/{}/repos", login),
+        };
         let mut all_repos = Vec::new();
-        let mut page = 1u32;
-        let per_page = 100u8;
-        let max_repos = 200; // Cap to avoid too many API calls
+        let mut page = 1usize;
 
         loop {
-            let repos = self
-                .client
-                .users(&self.username)
-                .repos()
-                .per_page(per_page)
-                .page(page)
-                .send()
-                .await?;
-
-            if repos.items.is_empty() {
-                break;
+            let url = format!("{}?per_page={}&page={}", base, REPOS_PER_PAGE, page);
+            let start = (page - 1) * REPOS_PER_PAGE;
+            let cached = previous.get(start..previous.len().min(start + REPOS_PER_PAGE));
+            if cached.is_none_or(|c| c.is_empty()) {
+                // Nothing to fall back on, so don't let the server say 304
+                validators.remove(&url);
             }
 
-            for repo in repos.items {
-                all_repos.push(GithubRepo {
-                    name: repo.name,
-                    full_name: repo.full_name.unwrap_or_default(),
-                    description: repo.description,
-                    html_url: repo.html_url.map(|u| u.to_string()).unwrap_or_default(),
-                    stargazers_count: repo.stargazers_count.unwrap_or(0) as u32,
-                    forks_count: repo.forks_count.unwrap_or(0) as u32,
-                    watchers_count: repo.watchers_count.unwrap_or(0) as u32,
-                    language: repo
-                        .language
-                        .and_then(|v| v.as_str().map(|s| s.to_string())),
-                    updated_at: repo.updated_at,
-                    pushed_at: repo.pushed_at,
-                    open_issues_count: repo.open_issues_count.unwrap_or(0) as u32,
-                    fork: repo.fork.unwrap_or(false),
-                });
-
-                if all_repos.len() >= max_repos {
-                    break;
+            let page_repos = match self.get_conditional(&url, validators).await?.body {
+                Some(body) => {
+                    let repos: Vec<octocrab::models::Repository> = serde_json::from_value(body)?;
+                    repos.into_iter().map(convert_repo).collect()
                 }
-            }
+                None => cached.map(|c| c.to_vec()).unwrap_or_default(),
+            };
 
-            if all_repos.len() >= max_repos {
+            if page_repos.is_empty() {
                 break;
             }
-
-            page += 1;
-            if page > 10 {
-                // Safety limit
+            let full_page = page_repos.len() >= REPOS_PER_PAGE;
+            all_repos.extend(page_repos);
+            if !full_page {
                 break;
             }
+            if page == REPOS_MAX_PAGES {
+                return Ok((all_repos, true));
+            }
+            page += 1;
         }
 
-        Ok(all_repos)
+        Ok((all_repos, false))
     }
 
     /// Fetch user events
     async fn fetch_events(
         &self,
         existing_ids: &std::collections::HashSet<String>,
-    ) -> Result<Vec<GithubEvent>> {
+        validators: &mut BTreeMap<String, Validators>,
+    ) -> Result<(Option<Vec<GithubEvent>>, Option<u64>)> {
         // Use the activity API to get user events
         let url = format!("/users/{}/events?per_page=50", self.username);
-        let response: Vec<serde_json::Value> = self.client.get(&url, None::<&()>).await?;
-
-        let mut events = Vec::new();
-
-        for event in response {
-            if let (Some(id), Some(event_type), Some(repo), Some(created_at)) = (
-                event.get("id").and_then(|v| v.as_str()),
-                event.get("type").and_then(|v| v.as_str()),
-                event
-                    .get("repo")
-                    .and_then(|v| v.get("name"))
-                    .and_then(|v| v.as_str()),
-                event.get("created_at").and_then(|v| v.as_str()),
-            ) {
-                let is_new = !existing_ids.contains(id);
-
-                if let Ok(created_at) = chrono::DateTime::parse_from_rfc3339(created_at) {
-                    events.push(GithubEvent {
-                        id: id.to_string(),
-                        event_type: GithubEventType::from_str(event_type),
-                        repo_name: repo.to_string(),
-                        created_at: created_at.with_timezone(&Utc),
-                        is_new,
+        self.fetch_event_list(&url, existing_ids, validators).await
+    }
+
+    /// Fetch and parse an events listing (None if unchanged since the last fetch)
+    async fn fetch_event_list(
+        &self,
+        url: &str,
+        existing_ids: &std::collections::HashSet<String>,
+        validators: &mut BTreeMap<String, Validators>,
+    ) -> Result<(Option<Vec<GithubEvent>>, Option<u64>)> {
+        let conditional = self.get_conditional(url, validators).await?;
+        let poll_interval = conditional.poll_interval_secs;
+        let Some(response) = conditional.body else {
+            return Ok((None, poll_interval));
+        };
+        let response = response.as_array().cloned().unwrap_or_default();
+
+        let events = response
+            .iter()
+            .filter_map(|event| parse_event(event, &self.username, existing_ids))
+            .collect();
+
+        Ok((Some(events), poll_interval))
+    }
+
+    /// Diff stargazer and follower lists against what we've seen before and
+    /// add feed entries for new people. Lists are only fetched when a count
+    /// went up since the previous state, so this is cheap between changes.
+    async fn detect_new_fans(&self, previous: &GithubState, state: &mut GithubState) {
+        let now = Utc::now();
+        let mut found = Vec::new();
+
+        // Only this refresh's finds are highlighted
+        for event in &mut state.announcements {
+            event.is_new = false;
+        }
+
+        // Followers come back newest first
+        let gained = match (&previous.profile, &state.profile) {
+            (Some(before), Some(after)) => after.followers.saturating_sub(before.followers),
+            _ => 0,
+        };
+        if gained > 0 {
+            let url = format!("/users/{}/followers?per_page=100", self.username);
+            match self.fetch_logins(&url).await {
+                Ok(logins) => {
+                    let newest = newest_unseen(&logins, &state.known_followers, gained, false);
+                    found.extend(newest.iter().map(|l| GithubEvent::followed(l, now)));
+                    state.known_followers.extend(logins);
+                }
+                Err(e) => warn!("Failed to fetch followers: {}", e),
+            }
+        }
+
+        // Stargazers come back oldest first, so the newest are on the last page
+        for repo in state.repos.iter().filter(|r| !r.fork) {
+            let Some(before) = previous
+                .repos
+                .iter()
+                .find(|r| r.full_name == repo.full_name)
+            else {
+                continue;
+            };
+            let gained = repo
+                .stargazers_count
+                .saturating_sub(before.stargazers_count);
+            if gained == 0 {
+                continue;
+            }
+            let last_page = repo.stargazers_count.div_ceil(100).max(1);
+            let url = format!(
+                "/repos/{}/stargazers?per_page=100&page={}",
+                repo.full_name, last_page
+            );
+            match self.fetch_logins(&url).await {
+                Ok(logins) => {
+                    let known = state
+                        .known_stargazers
+                        .entry(repo.full_name.clone())
+                        .or_default();
+                    let newest = newest_unseen(&logins, known, gained, true);
+                    found.extend(
+                        newest
+                            .iter()
+                            .map(|l| GithubEvent::starred(l, &repo.full_name, now)),
+                    );
+                    known.extend(logins);
+                }
+                Err(e) => warn!("Failed to fetch stargazers for {}: {}", repo.full_name, e),
+            }
+        }
+
+        if !found.is_empty() {
+            info!("{} new stargazers/followers", found.len());
+            found.append(&mut state.announcements);
+            found.truncate(MAX_ANNOUNCEMENTS);
+            state.announcements = found;
+        }
+    }
+
+    /// Fetch everything for the repository detail page. Only the repo itself
+    /// is required; the other parts are left empty when they fail.
+    pub async fn fetch_repo_detail(&self, full_name: &str) -> Result<RepoDetail> {
+        let base = format!("/repos/{}", full_name);
+        let repo: serde_json::Value = self.client.get(&base, None::<&()>).await?;
+        let branch = repo["default_branch"].as_str().unwrap_or("main");
+        let can_push = repo["permissions"]["push"].as_bool().unwrap_or(false);
+
+        let languages = self
+            .client
+            .get::<serde_json::Value, _, _>(format!("{}/languages", base), None::<&()>)
+            .await
+            .map(|v| parse_languages(&v))
+            .unwrap_or_else(|e| {
+                warn!("Failed to fetch languages of {}: {}", full_name, e);
+                Vec::new()
+            });
+
+        let commits_url = format!(
+            "{}/commits?sha={}&per_page={}",
+            base,
+            urlencoding::encode(branch),
+            REPO_DETAIL_COMMITS
+        );
+        let commits = self
+            .client
+            .get::<serde_json::Value, _, _>(commits_url, None::<&()>)
+            .await
+            .map(|v| parse_commits(&v))
+            .unwrap_or_else(|e| {
+                warn!("Failed to fetch commits of {}: {}", full_name, e);
+                Vec::new()
+            });
+
+        // The repo's open_issues_count includes PRs, so count those separately
+        let search_url = format!(
+            "/search/issues?q={}&per_page=1",
+            urlencoding::encode(&format!("repo:{} is:pr is:open", full_name))
+        );
+        let open_prs = match self
+            .client
+            .get::<serde_json::Value, _, _>(search_url, None::<&()>)
+            .await
+        {
+            Ok(v) => v["total_count"].as_u64().map(|n| n as u32),
+            Err(e) => {
+                warn!("Failed to count open PRs of {}: {}", full_name, e);
+                None
+            }
+        };
+        let open_issues_and_prs = repo["open_issues_count"].as_u64().unwrap_or(0) as u32;
+
+        // 404 just means there are no releases
+        let latest_release = self
+            .client
+            .get::<serde_json::Value, _, _>(format!("{}/releases/latest", base), None::<&()>)
+            .await
+            .ok()
+            .and_then(|v| parse_release(&v));
+
+        let traffic = if can_push {
+            let views = self
+                .client
+                .get::<serde_json::Value, _, _>(format!("{}/traffic/views", base), None::<&()>)
+                .await;
+            let clones = self
+                .client
+                .get::<serde_json::Value, _, _>(format!("{}/traffic/clones", base), None::<&()>)
+                .await;
+            match (views, clones) {
+                (Ok(views), Ok(clones)) => Some(RepoTraffic {
+                    views: parse_traffic(&views, "views"),
+                    clones: parse_traffic(&clones, "clones"),
+                }),
+                (Err(e), _) | (_, Err(e)) => {
+                    warn!("Failed to fetch traffic of {}: {}", full_name, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(RepoDetail {
+            full_name: full_name.to_string(),
+            languages,
+            commits,
+            open_prs,
+            open_issues: open_issues_and_prs.saturating_sub(open_prs.unwrap_or(0)),
+            latest_release,
+            topics: repo["topics"]
+                .as_array()
+                .map(|t| {
+                    t.iter()
+                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            license: repo["license"]["spdx_id"]
+                .as_str()
+                .filter(|id| *id != "NOASSERTION")
+                .or(repo["license"]["name"].as_str())
+                .map(|l| l.to_string()),
+            traffic,
+            fetched_at: Some(Utc::now()),
+            error: None,
+        })
+    }
+
+    /// Refresh byte counts per language for the most recently pushed non-fork
+    /// repos. Repos whose languages are unchanged answer 304 for free.
+    async fn fetch_language_bytes(&self, state: &mut GithubState) {
+        let mut repos: Vec<&GithubRepo> = state.repos.iter().filter(|r| !r.fork).collect();
+        repos.sort_by_key(|r| std::cmp::Reverse(r.pushed_at));
+        let names: Vec<String> = repos
+            .into_iter()
+            .take(LANGUAGE_BYTES_MAX_REPOS)
+            .map(|r| r.full_name.clone())
+            .collect();
+
+        let mut language_bytes = BTreeMap::new();
+        for name in names {
+            let url = format!("/repos/{}/languages", name);
+            let previous = state.language_bytes.remove(&name);
+            if previous.is_none() {
+                state.validators.remove(&url);
+            }
+            match self.get_conditional(&url, &mut state.validators).await {
+                Ok(response) => {
+                    let languages = match response.body {
+                        Some(body) => parse_languages(&body),
+                        None => previous.unwrap_or_default(),
+                    };
+                    language_bytes.insert(name, languages);
+                }
+                Err(e) => {
+                    warn!("Failed to fetch languages of {}: {}", name, e);
+                    note_throttle(&mut state.poll_hints, &e);
+                    if let Some(previous) = previous {
+                        language_bytes.insert(name, previous);
+                    }
+                }
+            }
+        }
+        state.language_bytes = language_bytes;
+    }
+
+    /// Fetch the latest release of each watched repo, or its newest tag when
+    /// it has no releases. A release is left unacknowledged when its tag
+    /// changed since the last fetch; the first one seen for a repo is not.
+    async fn fetch_watched_releases(&self, state: &mut GithubState) {
+        let mut watched = Vec::new();
+        for repo in &self.watch_releases {
+            let previous = state
+                .watched_releases
+                .iter()
+                .find(|r| r.repo.eq_ignore_ascii_case(repo))
+                .cloned();
+            match self
+                .fetch_latest_release(repo, previous.as_ref(), &mut state.validators)
+                .await
+            {
+                Ok(Some((release, is_tag))) => {
+                    let acknowledged = previous
+                        .as_ref()
+                        .is_none_or(|p| p.acknowledged && p.release.tag == release.tag);
+                    watched.push(WatchedRelease {
+                        repo: repo.clone(),
+                        release,
+                        is_tag,
+                        acknowledged,
                     });
                 }
+                Ok(None) => debug!("{} has no releases or tags", repo),
+                Err(e) => {
+                    warn!("Failed to fetch releases of {}: {}", repo, e);
+                    note_throttle(&mut state.poll_hints, &e);
+                    watched.extend(previous);
+                }
+            }
+        }
+        state.watched_releases = watched;
+    }
+
+    /// Newest release of a repo, falling back to its newest tag. Lists that
+    /// come back 304 reuse `previous`.
+    async fn fetch_latest_release(
+        &self,
+        repo: &str,
+        previous: Option<&WatchedRelease>,
+        validators: &mut BTreeMap<String, Validators>,
+    ) -> Result<Option<(ReleaseSummary, bool)>> {
+        let releases_url = format!("/repos/{}/releases?per_page=1", repo);
+        let tags_url = format!("/repos/{}/tags?per_page=1", repo);
+        // Without something to fall back on, don't let the server say 304
+        if previous.is_none() {
+            validators.remove(&releases_url);
+        }
+        if previous.is_none_or(|p| !p.is_tag) {
+            validators.remove(&tags_url);
+        }
+
+        // A 304 for a tag-only repo means there are still no releases
+        match self.get_conditional(&releases_url, validators).await?.body {
+            Some(body) => {
+                let latest = body
+                    .as_array()
+                    .and_then(|releases| releases.first())
+                    .and_then(parse_release);
+                if let Some(release) = latest {
+                    return Ok(Some((release, false)));
+                }
+            }
+            None => {
+                if let Some(p) = previous.filter(|p| !p.is_tag) {
+                    return Ok(Some((p.release.clone(), false)));
+                }
+            }
+        }
+
+        match self.get_conditional(&tags_url, validators).await?.body {
+            Some(body) => Ok(body
+                .as_array()
+                .and_then(|tags| tags.first())
+                .and_then(|tag| tag["name"].as_str())
+                .map(|name| {
+                    let release = ReleaseSummary {
+                        tag: name.to_string(),
+                        name: None,
+                        published_at: None,
+                    };
+                    (release, true)
+                })),
+            None => Ok(previous.map(|p| (p.release.clone(), true))),
+        }
+    }
+
+    /// Fetch a page of users and return their logins
+    async fn fetch_logins(&self, url: &str) -> Result<Vec<String>> {
+        let users: Vec<serde_json::Value> = self.client.get(url, None::<&()>).await?;
+        Ok(users
+            .iter()
+            .filter_map(|u| u["login"].as_str().map(|l| l.to_string()))
+            .collect())
+    }
+
+    /// Fetch the contribution calendar via GraphQL
+    async fn fetch_contributions(&self) -> Result<ContributionCalendar> {
+        let payload = serde_json::json!({
+            "query": CONTRIBUTIONS_QUERY,
+            "variables": { "login": self.username },
+        });
+        let response: serde_json::Value = self
+            .graphql
+            .post(&self.graphql_path, Some(&payload))
+            .await?;
+        parse_contributions(&response)
+    }
+
+    /// Fetch open PRs (authored and review-requested) and assigned issues
+    async fn fetch_inbox(&self) -> Result<Vec<InboxItem>> {
+        let user = &self.username;
+        let payload = serde_json::json!({
+            "query": INBOX_QUERY,
+            "variables": {
+                "authored": format!("is:open is:pr archived:false author:{}", user),
+                "review": format!("is:open is:pr archived:false review-requested:{}", user),
+                "assigned": format!("is:open is:issue archived:false assignee:{}", user),
+            },
+        });
+        let response: serde_json::Value = self
+            .graphql
+            .post(&self.graphql_path, Some(&payload))
+            .await?;
+        parse_inbox(&response)
+    }
+
+    /// Repos for the Actions board with their default branch (if known):
+    /// the configured list, or every non-fork repo that isn't archived,
+    /// most recently pushed first
+    fn actions_repos(&self, repos: &[GithubRepo]) -> Vec<(String, Option<String>)> {
+        let default_branch = |full_name: &str| {
+            repos
+                .iter()
+                .find(|r| r.full_name.eq_ignore_ascii_case(full_name))
+                .and_then(|r| r.default_branch.clone())
+        };
+
+        if !self.actions_repos.is_empty() {
+            return self
+                .actions_repos
+                .iter()
+                .map(|name| {
+                    let full_name = if name.contains('/') {
+                        name.clone()
+                    } else {
+                        format!("{}/{}", self.username, name)
+                    };
+                    let branch = default_branch(&full_name);
+                    (full_name, branch)
+                })
+                .collect();
+        }
+
+        let mut own: Vec<_> = repos.iter().filter(|r| !r.fork && !r.archived).collect();
+        own.sort_by_key(|r| std::cmp::Reverse(r.pushed_at));
+        own.into_iter()
+            .map(|r| (r.full_name.clone(), r.default_branch.clone()))
+            .collect()
+    }
+
+    /// Fetch recent workflow runs for each repo. Requests are conditional,
+    /// so a repo where nothing ran since the last refresh answers 304 for
+    /// free and keeps its runs. Failures are per repo (e.g. Actions
+    /// disabled): they're logged and the previous runs are kept.
+    async fn fetch_workflow_runs(
+        &self,
+        repos: &[(String, Option<String>)],
+        state: &mut GithubState,
+    ) {
+        let previous = std::mem::take(&mut state.workflow_runs);
+        let previous_runs = |full_name: &str| -> Vec<WorkflowRun> {
+            previous
+                .iter()
+                .filter(|r| r.repo_name.eq_ignore_ascii_case(full_name))
+                .cloned()
+                .collect()
+        };
+
+        let mut runs = Vec::new();
+        for (full_name, default_branch) in repos {
+            let url = format!("/repos/{}/actions/runs?per_page=20", full_name);
+            let kept = previous_runs(full_name);
+            if kept.is_empty() {
+                // A 304 would leave nothing to show
+                state.validators.remove(&url);
+            }
+            match self.get_conditional(&url, &mut state.validators).await {
+                Ok(response) => match response.body {
+                    Some(body) => {
+                        runs.extend(parse_workflow_runs(&body, default_branch.as_deref()))
+                    }
+                    None => runs.extend(kept),
+                },
+                Err(e) => {
+                    warn!("Failed to fetch workflow runs for {}: {}", full_name, e);
+                    note_throttle(&mut state.poll_hints, &e);
+                    runs.extend(kept);
+                }
             }
         }
+        state.workflow_runs = runs;
+    }
+
+    /// Fetch unread notification threads
+    async fn fetch_notifications(&self) -> Result<Vec<Notification>> {
+        let response: Vec<serde_json::Value> = self
+            .client
+            .get("/notifications?per_page=50", None::<&()>)
+            .await?;
+        Ok(response.iter().filter_map(parse_notification).collect())
+    }
+
+    /// Mark a single notification thread as read
+    pub async fn mark_notification_read(&self, thread_id: &str) -> Result<()> {
+        let id: u64 = thread_id.parse()?;
+        self.client
+            .activity()
+            .notifications()
+            .mark_as_read(octocrab::models::NotificationId(id))
+            .await?;
+        Ok(())
+    }
 
-        Ok(events)
+    /// Mark all notifications as read
+    pub async fn mark_all_notifications_read(&self) -> Result<()> {
+        self.client
+            .activity()
+            .notifications()
+            .mark_all_as_read(None)
+            .await?;
+        Ok(())
     }
 
     /// Fetch rate limit information
@@ -248,3 +1096,1169 @@ impl GithubClient {
         })
     }
 }
+
+/// Build an Octocrab client for `base_uri`. With a CA bundle configured the
+/// HTTPS stack is assembled by hand so the extra roots can be trusted.
+fn build_octocrab(config: &Config, base_uri: &str) -> Result<Octocrab> {
+    let Some(ref bundle) = config.github_ca_bundle else {
+        let builder = match config.github_token {
+            Some(ref token) => Octocrab::builder().personal_token(token.clone()),
+            None => Octocrab::builder(),
+        };
+        return Ok(builder.base_uri(base_uri)?.build()?);
+    };
+
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config(bundle)?)
+        .https_or_http()
+        .enable_http1()
+        .build();
+    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+        .build(connector);
+
+    let base: http::Uri = base_uri.parse()?;
+    let auth_header = config
+        .github_token
+        .as_ref()
+        .map(|token| HeaderValue::from_str(&format!("Bearer {}", token)))
+        .transpose()?;
+    let user_agent = vec![(USER_AGENT, HeaderValue::from_static("octocrab"))];
+
+    Ok(octocrab::OctocrabBuilder::new_empty()
+        .with_service(client)
+        .with_layer(&BaseUriLayer::new(base.clone()))
+        .with_layer(&ExtraHeadersLayer::new(Arc::new(user_agent)))
+        .with_layer(&AuthHeaderLayer::new(auth_header, base.clone(), base))
+        .with_auth(octocrab::AuthState::None)
+        .build()?)
+}
+
+/// TLS settings trusting the system roots plus every certificate in `bundle`
+fn tls_config(bundle: &Path) -> Result<rustls::ClientConfig> {
+    let mut roots = rustls::RootCertStore::empty();
+    let native = rustls_native_certs::load_native_certs();
+    for error in &native.errors {
+        warn!("Skipping system certificates: {}", error);
+    }
+    roots.add_parsable_certificates(native.certs);
+
+    let extra = CertificateDer::pem_file_iter(bundle)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| anyhow::anyhow!("failed to read CA bundle {}: {}", bundle.display(), e))?;
+    if extra.is_empty() {
+        anyhow::bail!("no certificates found in CA bundle {}", bundle.display());
+    }
+    let (added, _) = roots.add_parsable_certificates(extra);
+    info!("Trusting {} certificates from {}", added, bundle.display());
+
+    Ok(rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
+
+/// Languages from `/repos/{repo}/languages`, largest first
+fn parse_languages(languages: &serde_json::Value) -> Vec<(String, u64)> {
+    let mut languages: Vec<(String, u64)> = languages
+        .as_object()
+        .map(|langs| {
+            langs
+                .iter()
+                .map(|(name, bytes)| (name.clone(), bytes.as_u64().unwrap_or(0)))
+                .collect()
+        })
+        .unwrap_or_default();
+    languages.sort_by_key(|(_, bytes)| std::cmp::Reverse(*bytes));
+    languages
+}
+
+/// Commits from `/repos/{repo}/commits`
+fn parse_commits(commits: &serde_json::Value) -> Vec<CommitSummary> {
+    commits
+        .as_array()
+        .map(|commits| {
+            commits
+                .iter()
+                .filter_map(|c| {
+                    let commit = &c["commit"];
+                    Some(CommitSummary {
+                        sha: c["sha"].as_str()?.chars().take(7).collect(),
+                        message: commit["message"].as_str()?.lines().next()?.to_string(),
+                        // Prefer the GitHub login over the git author name
+                        author: c["author"]["login"]
+                            .as_str()
+                            .or(commit["author"]["name"].as_str())
+                            .unwrap_or("unknown")
+                            .to_string(),
+                        date: commit["author"]["date"]
+                            .as_str()
+                            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                            .map(|t| t.with_timezone(&Utc)),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The release from `/repos/{repo}/releases/latest`
+fn parse_release(release: &serde_json::Value) -> Option<ReleaseSummary> {
+    Some(ReleaseSummary {
+        tag: release["tag_name"].as_str()?.to_string(),
+        name: release["name"]
+            .as_str()
+            .filter(|n| !n.is_empty())
+            .map(|n| n.to_string()),
+        published_at: release["published_at"]
+            .as_str()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc)),
+    })
+}
+
+/// Daily counts from `/traffic/views` or `/traffic/clones` (`key` names the list)
+fn parse_traffic(traffic: &serde_json::Value, key: &str) -> Vec<TrafficDay> {
+    traffic[key]
+        .as_array()
+        .map(|days| {
+            days.iter()
+                .filter_map(|d| {
+                    Some(TrafficDay {
+                        date: chrono::DateTime::parse_from_rfc3339(d["timestamp"].as_str()?)
+                            .ok()?
+                            .date_naive(),
+                        count: d["count"].as_u64().unwrap_or(0) as u32,
+                        uniques: d["uniques"].as_u64().unwrap_or(0) as u32,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse one entry from an events listing. The actor is kept only when it
+/// isn't `username`, e.g. someone else's activity on the user's repos.
+fn parse_event(
+    event: &serde_json::Value,
+    username: &str,
+    existing_ids: &std::collections::HashSet<String>,
+) -> Option<GithubEvent> {
+    let id = event["id"].as_str()?;
+    let event_type = GithubEventType::from_str(event["type"].as_str()?);
+    let created_at = chrono::DateTime::parse_from_rfc3339(event["created_at"].as_str()?).ok()?;
+    let actor = event["actor"]["login"]
+        .as_str()
+        .filter(|login| !login.eq_ignore_ascii_case(username))
+        .map(|login| login.to_string());
+    Some(GithubEvent {
+        id: id.to_string(),
+        payload: parse_payload(&event_type, &event["payload"]),
+        event_type,
+        repo_name: event["repo"]["name"].as_str()?.to_string(),
+        created_at: created_at.with_timezone(&Utc),
+        is_new: !existing_ids.contains(id),
+        actor,
+    })
+}
+
+/// Pull the interesting details out of an event payload
+fn parse_payload(event_type: &GithubEventType, payload: &serde_json::Value) -> EventPayload {
+    let text = |v: &serde_json::Value| v.as_str().unwrap_or("").to_string();
+    match event_type {
+        GithubEventType::PushEvent => {
+            let commits = payload["commits"].as_array().cloned().unwrap_or_default();
+            let messages = commits
+                .iter()
+                .filter_map(|c| c["message"].as_str())
+                .filter_map(|m| m.lines().next())
+                .take(MAX_COMMIT_MESSAGES)
+                .map(|m| m.to_string())
+                .collect();
+            let count = payload["size"].as_u64().unwrap_or(commits.len() as u64);
+            EventPayload::Push {
+                branch: text(&payload["ref"])
+                    .trim_start_matches("refs/heads/")
+                    .to_string(),
+                commits: count as u32,
+                messages,
+            }
+        }
+        GithubEventType::CreateEvent | GithubEventType::DeleteEvent => EventPayload::Ref {
+            ref_type: text(&payload["ref_type"]),
+            name: payload["ref"].as_str().map(|r| r.to_string()),
+        },
+        GithubEventType::IssuesEvent | GithubEventType::IssueCommentEvent => {
+            let action = match event_type {
+                GithubEventType::IssueCommentEvent => "commented".to_string(),
+                _ => text(&payload["action"]),
+            };
+            EventPayload::Issue {
+                action,
+                number: payload["issue"]["number"].as_u64().unwrap_or(0),
+                title: text(&payload["issue"]["title"]),
+            }
+        }
+        GithubEventType::PullRequestEvent | GithubEventType::PullRequestReviewEvent => {
+            let pr = &payload["pull_request"];
+            let action = match event_type {
+                GithubEventType::PullRequestReviewEvent => {
+                    text(&payload["review"]["state"]).to_lowercase()
+                }
+                _ if payload["action"] == "closed" && pr["merged"] == true => "merged".to_string(),
+                _ => text(&payload["action"]),
+            };
+            EventPayload::Issue {
+                action,
+                number: pr["number"]
+                    .as_u64()
+                    .or(payload["number"].as_u64())
+                    .unwrap_or(0),
+                title: text(&pr["title"]),
+            }
+        }
+        GithubEventType::ReleaseEvent => EventPayload::Release {
+            action: text(&payload["action"]),
+            tag: text(&payload["release"]["tag_name"]),
+        },
+        _ => EventPayload::None,
+    }
+}
+
+/// Parse a `/users/{login}` response, which also describes organizations
+fn parse_profile(user: &serde_json::Value) -> Option<(GithubProfile, AccountKind)> {
+    let count = |key: &str| user[key].as_u64().unwrap_or(0) as u32;
+    let kind = match user["type"].as_str() {
+        Some("Organization") => AccountKind::Organization,
+        _ => AccountKind::User,
+    };
+    let profile = GithubProfile {
+        login: user["login"].as_str()?.to_string(),
+        name: user["name"].as_str().map(|s| s.to_string()),
+        avatar_url: user["avatar_url"].as_str().unwrap_or("").to_string(),
+        bio: user["bio"].as_str().map(|s| s.to_string()),
+        public_repos: count("public_repos"),
+        public_gists: count("public_gists"),
+        followers: count("followers"),
+        following: count("following"),
+        created_at: user["created_at"]
+            .as_str()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc)),
+    };
+    Some((profile, kind))
+}
+
+/// Record in `hints` whether an error means the server wants us to slow down
+fn note_throttle(hints: &mut PollHints, e: &anyhow::Error) {
+    let retry_after = if let Some(throttled) = e.downcast_ref::<Throttled>() {
+        throttled.retry_after_secs
+    } else if let Some(octocrab::Error::GitHub { source, .. }) = e.downcast_ref::<octocrab::Error>()
+    {
+        let status = source.status_code;
+        let slow_down = status.is_server_error()
+            || status == StatusCode::TOO_MANY_REQUESTS
+            || source.message.contains("secondary rate limit");
+        if !slow_down {
+            return;
+        }
+        None
+    } else {
+        return;
+    };
+    hints.throttled = true;
+    hints.retry_after_secs = hints.retry_after_secs.max(retry_after);
+}
+
+/// Map an API repository into our model
+fn convert_repo(repo: octocrab::models::Repository) -> GithubRepo {
+    GithubRepo {
+        name: repo.name,
+        full_name: repo.full_name.unwrap_or_default(),
+        description: repo.description,
+        html_url: repo.html_url.map(|u| u.to_string()).unwrap_or_default(),
+        stargazers_count: repo.stargazers_count.unwrap_or(0) as u32,
+        forks_count: repo.forks_count.unwrap_or(0) as u32,
+        watchers_count: repo.watchers_count.unwrap_or(0) as u32,
+        language: repo
+            .language
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        updated_at: repo.updated_at,
+        pushed_at: repo.pushed_at,
+        open_issues_count: repo.open_issues_count.unwrap_or(0) as u32,
+        fork: repo.fork.unwrap_or(false),
+        default_branch: repo.default_branch,
+        archived: repo.archived.unwrap_or(false),
+    }
+}
+
+/// Up to `limit` logins not seen before, taken from the newest end of the
+/// list (the end when `oldest_first`, otherwise the start)
+fn newest_unseen(
+    logins: &[String],
+    known: &std::collections::BTreeSet<String>,
+    limit: u32,
+    oldest_first: bool,
+) -> Vec<String> {
+    let unseen = |l: &&String| !known.contains(*l);
+    if oldest_first {
+        logins
+            .iter()
+            .rev()
+            .filter(unseen)
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    } else {
+        logins
+            .iter()
+            .filter(unseen)
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Parse a contribution calendar GraphQL response
+fn parse_contributions(response: &serde_json::Value) -> Result<ContributionCalendar> {
+    if let Some(message) = response["errors"][0]["message"].as_str() {
+        anyhow::bail!("GraphQL error: {}", message);
+    }
+    let calendar = &response["data"]["user"]["contributionsCollection"]["contributionCalendar"];
+    if calendar.is_null() {
+        anyhow::bail!("no contribution calendar in response");
+    }
+
+    let days = calendar["weeks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|week| week["contributionDays"].as_array())
+        .flatten()
+        .filter_map(|day| {
+            Some(ContributionDay {
+                date: day["date"].as_str()?.parse().ok()?,
+                count: day["contributionCount"].as_u64().unwrap_or(0) as u32,
+                level: ContributionCalendar::level_from_str(
+                    day["contributionLevel"].as_str().unwrap_or(""),
+                ),
+            })
+        })
+        .collect();
+
+    Ok(ContributionCalendar {
+        total: calendar["totalContributions"].as_u64().unwrap_or(0) as u32,
+        days,
+    })
+}
+
+/// Parse one entry of the `/notifications` response
+fn parse_notification(thread: &serde_json::Value) -> Option<Notification> {
+    if !thread["unread"].as_bool().unwrap_or(true) {
+        return None;
+    }
+    let updated_at = chrono::DateTime::parse_from_rfc3339(thread["updated_at"].as_str()?).ok()?;
+    Some(Notification {
+        id: thread["id"].as_str()?.to_string(),
+        repo_name: thread["repository"]["full_name"].as_str()?.to_string(),
+        reason: NotificationReason::parse(thread["reason"].as_str().unwrap_or("")),
+        title: thread["subject"]["title"]
+            .as_str()
+            .unwrap_or("")
+            .to_string(),
+        subject_type: thread["subject"]["type"].as_str().unwrap_or("").to_string(),
+        updated_at: updated_at.with_timezone(&Utc),
+    })
+}
+
+/// Parse a `/actions/runs` response, keeping the latest run of each
+/// workflow per branch. Without a known default branch, `main` and
+/// `master` count as default.
+fn parse_workflow_runs(
+    response: &serde_json::Value,
+    default_branch: Option<&str>,
+) -> Vec<WorkflowRun> {
+    let mut seen = std::collections::HashSet::new();
+    let mut runs = Vec::new();
+
+    // The API returns runs newest first
+    for run in response["workflow_runs"].as_array().into_iter().flatten() {
+        let Some(branch) = run["head_branch"].as_str() else {
+            continue;
+        };
+        if !seen.insert((run["workflow_id"].as_u64(), branch.to_string())) {
+            continue;
+        }
+        let parse_time = |key: &str| {
+            run[key]
+                .as_str()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc))
+        };
+        let (Some(id), Some(updated_at)) = (run["id"].as_u64(), parse_time("updated_at")) else {
+            continue;
+        };
+        let on_default_branch = match default_branch {
+            Some(default) => branch == default,
+            None => branch == "main" || branch == "master",
+        };
+
+        runs.push(WorkflowRun {
+            id,
+            repo_name: run["repository"]["full_name"]
+                .as_str()
+                .unwrap_or("")
+                .to_string(),
+            workflow_name: run["name"].as_str().unwrap_or("workflow").to_string(),
+            branch: branch.to_string(),
+            state: RunState::parse(
+                run["status"].as_str().unwrap_or(""),
+                run["conclusion"].as_str(),
+            ),
+            url: run["html_url"].as_str().unwrap_or("").to_string(),
+            started_at: parse_time("run_started_at")
+                .or_else(|| parse_time("created_at"))
+                .unwrap_or(updated_at),
+            updated_at,
+            on_default_branch,
+        });
+    }
+    runs
+}
+
+/// Parse an inbox GraphQL response
+fn parse_inbox(response: &serde_json::Value) -> Result<Vec<InboxItem>> {
+    if let Some(message) = response["errors"][0]["message"].as_str() {
+        anyhow::bail!("GraphQL error: {}", message);
+    }
+
+    let sections = [
+        ("authored", InboxKind::AuthoredPr),
+        ("review", InboxKind::ReviewRequested),
+        ("assigned", InboxKind::AssignedIssue),
+    ];
+    let mut items = Vec::new();
+    for (alias, kind) in sections {
+        let nodes = response["data"][alias]["nodes"].as_array();
+        for node in nodes.into_iter().flatten() {
+            if let Some(item) = parse_inbox_item(node, kind) {
+                items.push(item);
+            }
+        }
+    }
+    Ok(items)
+}
+
+fn parse_inbox_item(node: &serde_json::Value, kind: InboxKind) -> Option<InboxItem> {
+    let timestamp = |key: &str| {
+        chrono::DateTime::parse_from_rfc3339(node[key].as_str()?)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    };
+
+    Some(InboxItem {
+        kind,
+        repo_name: node["repository"]["nameWithOwner"].as_str()?.to_string(),
+        number: node["number"].as_u64()?,
+        title: node["title"].as_str().unwrap_or("").to_string(),
+        url: node["url"].as_str().unwrap_or("").to_string(),
+        // Deleted accounts have a null author
+        author: node["author"]["login"]
+            .as_str()
+            .unwrap_or("ghost")
+            .to_string(),
+        created_at: timestamp("createdAt")?,
+        updated_at: timestamp("updatedAt")?,
+        labels: node["labels"]["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|l| l["name"].as_str().map(|s| s.to_string()))
+            .collect(),
+        is_draft: node["isDraft"].as_bool().unwrap_or(false),
+        ci: node["commits"]["nodes"][0]["commit"]["statusCheckRollup"]["state"]
+            .as_str()
+            .and_then(CiStatus::parse),
+        review: node["reviewDecision"].as_str().and_then(ReviewState::parse),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_contributions() {
+        let response = serde_json::json!({
+            "data": { "user": { "contributionsCollection": { "contributionCalendar": {
+                "totalContributions": 5,
+                "weeks": [
+                    { "contributionDays": [
+                        { "date": "2026-10-11", "contributionCount": 0, "contributionLevel": "NONE" },
+                        { "date": "2026-10-12", "contributionCount": 5, "contributionLevel": "FOURTH_QUARTILE" }
+                    ] }
+                ]
+            } } } }
+        });
+        let calendar = parse_contributions(&response).unwrap();
+        assert_eq!(calendar.total, 5);
+        assert_eq!(calendar.days.len(), 2);
+        assert_eq!(calendar.days[1].count, 5);
+        assert_eq!(calendar.days[1].level, 4);
+
+        let error = serde_json::json!({ "errors": [{ "message": "Bad credentials" }] });
+        assert!(parse_contributions(&error).is_err());
+    }
+
+    #[test]
+    fn test_parse_inbox() {
+        let pr = |number: u64, ci: &str, review: serde_json::Value| {
+            serde_json::json!({
+                "number": number,
+                "title": "Fix the thing",
+                "url": format!("https://github.com/acme/app/pull/{}", number),
+                "createdAt": "2026-10-01T10:00:00Z",
+                "updatedAt": "2026-10-02T10:00:00Z",
+                "isDraft": false,
+                "reviewDecision": review,
+                "author": { "login": "octocat" },
+                "repository": { "nameWithOwner": "acme/app" },
+                "labels": { "nodes": [{ "name": "bug" }] },
+                "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "state": ci } } }] }
+            })
+        };
+        let response = serde_json::json!({ "data": {
+            "authored": { "nodes": [pr(1, "FAILURE", serde_json::json!("APPROVED"))] },
+            "review": { "nodes": [pr(2, "PENDING", serde_json::Value::Null)] },
+            "assigned": { "nodes": [{
+                "number": 7,
+                "title": "Crash on start",
+                "url": "https://github.com/acme/app/issues/7",
+                "createdAt": "2026-09-01T10:00:00Z",
+                "updatedAt": "2026-09-02T10:00:00Z",
+                "author": null,
+                "repository": { "nameWithOwner": "acme/app" },
+                "labels": { "nodes": [] }
+            }] }
+        } });
+
+        let items = parse_inbox(&response).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].kind, InboxKind::AuthoredPr);
+        assert_eq!(items[0].ci, Some(CiStatus::Failure));
+        assert_eq!(items[0].review, Some(ReviewState::Approved));
+        assert_eq!(items[0].labels, vec!["bug".to_string()]);
+        assert_eq!(items[1].kind, InboxKind::ReviewRequested);
+        assert_eq!(items[1].ci, Some(CiStatus::Pending));
+        assert_eq!(items[1].review, None);
+        assert_eq!(items[2].kind, InboxKind::AssignedIssue);
+        assert_eq!(items[2].author, "ghost");
+        assert_eq!(items[2].ci, None);
+    }
+
+    #[test]
+    fn test_parse_notification() {
+        let thread = serde_json::json!({
+            "id": "123",
+            "unread": true,
+            "reason": "ci_activity",
+            "updated_at": "2026-10-02T10:00:00Z",
+            "subject": { "title": "CI failed on main", "type": "CheckSuite" },
+            "repository": { "full_name": "acme/app" }
+        });
+        let n = parse_notification(&thread).unwrap();
+        assert_eq!(n.id, "123");
+        assert_eq!(n.repo_name, "acme/app");
+        assert_eq!(n.reason, NotificationReason::CiActivity);
+        assert_eq!(n.subject_type, "CheckSuite");
+
+        let mut read = thread.clone();
+        read["unread"] = serde_json::json!(false);
+        assert!(parse_notification(&read).is_none());
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let seen = std::collections::HashSet::from(["1".to_string()]);
+        let push = serde_json::json!({
+            "id": "1", "type": "PushEvent", "created_at": "2026-10-12T10:00:00Z",
+            "actor": { "login": "Octocat" }, "repo": { "name": "octocat/app" },
+            "payload": { "ref": "refs/heads/main", "size": 3, "commits": [
+                { "message": "Fix parser\n\nLong body" },
+                { "message": "Add tests" },
+                { "message": "Bump version" }
+            ] }
+        });
+        let event = parse_event(&push, "octocat", &seen).unwrap();
+        assert!(!event.is_new);
+        assert_eq!(event.actor, None);
+        assert_eq!(event.description(), "pushed 3 commits to main in");
+        assert_eq!(event.detail_lines(), vec!["Fix parser", "Add tests"]);
+
+        let merged = serde_json::json!({
+            "id": "2", "type": "PullRequestEvent", "created_at": "2026-10-12T11:00:00Z",
+            "actor": { "login": "alice" }, "repo": { "name": "octocat/app" },
+            "payload": { "action": "closed", "number": 12,
+                "pull_request": { "number": 12, "title": "Faster builds", "merged": true } }
+        });
+        let event = parse_event(&merged, "octocat", &seen).unwrap();
+        assert!(event.is_new);
+        assert_eq!(event.actor.as_deref(), Some("alice"));
+        assert_eq!(event.summary(), "alice merged PR #12 in app");
+        assert_eq!(event.detail_lines(), vec!["Faster builds"]);
+
+        let tag = serde_json::json!({
+            "id": "3", "type": "CreateEvent", "created_at": "2026-10-12T12:00:00Z",
+            "repo": { "name": "octocat/app" },
+            "payload": { "ref_type": "tag", "ref": "v1.2.0" }
+        });
+        let event = parse_event(&tag, "octocat", &seen).unwrap();
+        assert_eq!(event.description(), "created tag v1.2.0 in");
+    }
+
+    #[test]
+    fn test_parse_repo_detail_parts() {
+        let languages = parse_languages(&serde_json::json!({ "Shell": 100, "Rust": 900 }));
+        assert_eq!(languages[0], ("Rust".to_string(), 900));
+        let detail = RepoDetail {
+            languages,
+            ..Default::default()
+        };
+        assert_eq!(detail.language_shares()[1], ("Shell", 10.0));
+
+        let commits = parse_commits(&serde_json::json!([{
+            "sha": "0123456789abcdef",
+            "author": null,
+            "commit": { "message": "Fix login\n\nDetails", "author": {
+                "name": "Mona", "date": "2026-10-12T10:00:00Z"
+            } }
+        }]));
+        assert_eq!(commits[0].sha, "0123456");
+        assert_eq!(commits[0].message, "Fix login");
+        assert_eq!(commits[0].author, "Mona");
+
+        let views = parse_traffic(
+            &serde_json::json!({ "count": 5, "uniques": 2, "views": [
+                { "timestamp": "2026-10-11T00:00:00Z", "count": 3, "uniques": 1 },
+                { "timestamp": "2026-10-12T00:00:00Z", "count": 2, "uniques": 2 }
+            ] }),
+            "views",
+        );
+        assert_eq!(views.len(), 2);
+        assert_eq!(
+            views[1].date,
+            chrono::NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+        );
+
+        assert!(parse_release(&serde_json::json!({ "message": "Not Found" })).is_none());
+    }
+
+    #[test]
+    fn test_parse_workflow_runs() {
+        let run = |id: u64, workflow: u64, branch: &str, status: &str, conclusion: Option<&str>| {
+            serde_json::json!({
+                "id": id,
+                "workflow_id": workflow,
+                "name": "CI",
+                "head_branch": branch,
+                "status": status,
+                "conclusion": conclusion,
+                "html_url": format!("https://github.com/acme/app/actions/runs/{}", id),
+                "run_started_at": "2026-10-02T10:00:00Z",
+                "updated_at": "2026-10-02T10:04:30Z",
+                "repository": { "full_name": "acme/app" }
+            })
+        };
+        let response = serde_json::json!({
+            "workflow_runs": [
+                run(3, 1, "trunk", "completed", Some("failure")),
+                run(2, 1, "feature", "in_progress", None),
+                run(1, 1, "trunk", "completed", Some("success")),
+            ]
+        });
+
+        let runs = parse_workflow_runs(&response, Some("trunk"));
+        // The older trunk run is superseded by run 3
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].id, 3);
+        assert_eq!(runs[0].state, RunState::Failure);
+        assert!(runs[0].is_broken_default());
+        assert_eq!(runs[0].duration(Utc::now()).num_seconds(), 270);
+        assert_eq!(runs[1].state, RunState::Running);
+        assert!(!runs[1].on_default_branch);
+    }
+
+    #[test]
+    fn test_newest_unseen() {
+        let logins: Vec<String> = ["old", "alice", "bob"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let known = std::collections::BTreeSet::from(["bob".to_string()]);
+
+        // Stargazers: oldest first, bob was already announced
+        assert_eq!(newest_unseen(&logins, &known, 1, true), vec!["alice"]);
+        // Followers: newest first
+        assert_eq!(
+            newest_unseen(&logins, &known, 2, false),
+            vec!["old", "alice"]
+        );
+    }
+
+    fn mock_config(api_url: String, graphql_url: String) -> Config {
+        Config {
+            github_token: None,
+            github_user: "octocat".to_string(),
+            extra_accounts: Vec::new(),
+            github_api_url: api_url,
+            github_graphql_url: graphql_url,
+            github_ca_bundle: None,
+            refresh_secs: 60,
+            reduced_motion: false,
+            store_dir: std::env::temp_dir().join("control-system-test"),
+            spotify: Default::default(),
+            actions_repos: Vec::new(),
+            actions_alert: false,
+            watch_releases: Vec::new(),
+            feed: Default::default(),
+            language_bytes: false,
+            connectivity_probe: String::new(),
+        }
+    }
+
+    fn mock_client(server: &wiremock::MockServer) -> GithubClient {
+        let config = mock_config(server.uri(), format!("{}/graphql", server.uri()));
+        GithubClient::new(&config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_enterprise_endpoints() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // GitHub Enterprise Server layout: REST under /api/v3, GraphQL at /api/graphql
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/users/octocat/events"))
+            .and(header("authorization", "Bearer t0ken"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/graphql"))
+            .and(header("authorization", "Bearer t0ken"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "user": { "contributionsCollection": { "contributionCalendar": {
+                    "totalContributions": 2,
+                    "weeks": [{ "contributionDays": [
+                        { "date": "2026-10-12", "contributionCount": 2, "contributionLevel": "FIRST_QUARTILE" }
+                    ] }]
+                } } } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = mock_config(
+            format!("{}/api/v3", server.uri()),
+            format!("{}/api/graphql", server.uri()),
+        );
+        config.github_token = Some("t0ken".to_string());
+        let client = GithubClient::new(&config).unwrap();
+
+        let mut validators = BTreeMap::new();
+        let (events, _) = client
+            .fetch_events(&Default::default(), &mut validators)
+            .await
+            .unwrap();
+        assert_eq!(events.unwrap().len(), 0);
+        let calendar = client.fetch_contributions().await.unwrap();
+        assert_eq!(calendar.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_org_account() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "login": "acme",
+                "type": "Organization",
+                "public_repos": 1,
+                "followers": 5
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Organizations list their repos under /orgs, not /users
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/repos"))
+            .and(query_param("page", "1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": 7,
+                    "name": "anvil",
+                    "full_name": "acme/anvil",
+                    "url": format!("{}/repos/acme/anvil", server.uri()),
+                    "stargazers_count": 12,
+                    "fork": false
+                }])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = mock_config(server.uri(), format!("{}/graphql", server.uri()));
+        let client = GithubClient::new(&config).unwrap();
+        let mut state = GithubState::default();
+        let mut errors = Vec::new();
+        let account = client
+            .fetch_account("acme", None, &mut state, &mut errors)
+            .await;
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(account.kind, AccountKind::Organization);
+        assert_eq!(account.profile.as_ref().unwrap().followers, 5);
+        assert_eq!(account.repos.len(), 1);
+        assert_eq!(account.repos[0].name, "anvil");
+
+        // `a` steps through the combined view and then each account
+        let mut app = crate::app::AppState::default();
+        app.github.accounts = vec![account.clone()];
+        app.cycle_account();
+        assert_eq!(app.ui.selected_account, None);
+        app.github.accounts.insert(
+            0,
+            GithubAccount {
+                login: "octocat".to_string(),
+                ..account
+            },
+        );
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            app.cycle_account();
+            seen.push(app.ui.selected_account);
+        }
+        assert_eq!(seen, [Some(0), Some(1), None]);
+        assert_eq!(app.github.account(Some(1)).unwrap().login, "acme");
+    }
+
+    #[tokio::test]
+    async fn test_permission_403_is_not_throttling() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // A token without access to one repo's Actions
+        Mock::given(method("GET"))
+            .and(path("/repos/me/locked/actions/runs"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("x-ratelimit-remaining", "4999")
+                    .set_body_json(serde_json::json!({
+                        "message": "Resource not accessible by personal access token"
+                    })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/me/busy/actions/runs"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("x-ratelimit-remaining", "4999")
+                    .set_body_json(serde_json::json!({
+                        "message": "You have exceeded a secondary rate limit"
+                    })),
+            )
+            .mount(&server)
+            .await;
+
+        let config = mock_config(server.uri(), format!("{}/graphql", server.uri()));
+        let client = GithubClient::new(&config).unwrap();
+        let mut validators = BTreeMap::new();
+        let mut hints = PollHints::default();
+
+        let denied = client
+            .get_conditional("/repos/me/locked/actions/runs", &mut validators)
+            .await
+            .err()
+            .unwrap();
+        note_throttle(&mut hints, &denied);
+        assert!(!hints.throttled);
+        assert!(format!("{:?}", denied).contains("Resource not accessible"));
+
+        let busy = client
+            .get_conditional("/repos/me/busy/actions/runs", &mut validators)
+            .await
+            .err()
+            .unwrap();
+        note_throttle(&mut hints, &busy);
+        assert!(hints.throttled);
+    }
+
+    #[tokio::test]
+    async fn test_valid_ca_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("ca.pem");
+        std::fs::write(&bundle, include_str!("../../tests/fixtures/ca_bundle.pem")).unwrap();
+        let mut config = mock_config(
+            "https://ghe.example.com/api/v3".to_string(),
+            "https://ghe.example.com/api/graphql".to_string(),
+        );
+        config.github_ca_bundle = Some(bundle);
+        assert!(GithubClient::new(&config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_ca_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("ca.pem");
+        std::fs::write(&bundle, "not a certificate").unwrap();
+        let mut config = mock_config(
+            "https://ghe.example.com/api/v3".to_string(),
+            "https://ghe.example.com/api/graphql".to_string(),
+        );
+        config.github_ca_bundle = Some(bundle);
+        let error = GithubClient::new(&config).err().unwrap();
+        assert!(error.to_string().contains("no certificates found"));
+    }
+
+    #[tokio::test]
+    async fn test_events_not_modified() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Registered first, so it wins whenever the validator is sent
+        Mock::given(method("GET"))
+            .and(path("/users/octocat/events"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/octocat/events"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .set_body_json(serde_json::json!([{
+                        "id": "1",
+                        "type": "PushEvent",
+                        "repo": { "name": "octocat/app" },
+                        "created_at": "2026-10-02T10:00:00Z"
+                    }])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let mut validators = BTreeMap::new();
+        let seen = std::collections::HashSet::new();
+
+        let (events, _) = client.fetch_events(&seen, &mut validators).await.unwrap();
+        assert_eq!(events.unwrap().len(), 1);
+        let url = "/users/octocat/events?per_page=50";
+        assert_eq!(validators[url].etag.as_deref(), Some("\"v1\""));
+
+        // Second refresh sends If-None-Match and gets 304
+        let (events, _) = client.fetch_events(&seen, &mut validators).await.unwrap();
+        assert!(events.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_watched_releases() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/tokio-rs/tokio/releases"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "tag_name": "tokio-1.48.0",
+                    "name": "Tokio v1.48.0",
+                    "published_at": "2026-10-10T12:00:00Z"
+                }])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/tool/releases"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/tool/tags"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{ "name": "v0.3.1" }])),
+            )
+            .mount(&server)
+            .await;
+
+        let mut config = mock_config(server.uri(), format!("{}/graphql", server.uri()));
+        config.watch_releases = vec!["tokio-rs/tokio".to_string(), "acme/tool".to_string()];
+        let client = GithubClient::new(&config).unwrap();
+
+        // The first release seen for a repo isn't announced
+        let mut state = GithubState {
+            watched_releases: vec![WatchedRelease {
+                repo: "acme/tool".to_string(),
+                release: ReleaseSummary {
+                    tag: "v0.3.1".to_string(),
+                    name: None,
+                    published_at: None,
+                },
+                is_tag: true,
+                acknowledged: true,
+            }],
+            ..Default::default()
+        };
+        client.fetch_watched_releases(&mut state).await;
+        let tokio = &state.watched_releases[0];
+        assert_eq!(tokio.release.tag, "tokio-1.48.0");
+        assert!(!tokio.is_tag && tokio.acknowledged);
+        let tool = &state.watched_releases[1];
+        assert!(tool.is_tag && tool.acknowledged);
+        assert_eq!(state.new_release_count(), 0);
+
+        // A changed tag is new until acknowledged
+        state.watched_releases[0].release.tag = "tokio-1.47.1".to_string();
+        client.fetch_watched_releases(&mut state).await;
+        assert!(!state.watched_releases[0].acknowledged);
+        assert_eq!(state.new_release_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_repos_not_modified_reuses_previous() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/octocat/repos"))
+            .and(query_param("page", "1"))
+            // `header()` splits values on commas, which breaks HTTP dates
+            .and(|req: &wiremock::Request| {
+                req.headers
+                    .get("if-modified-since")
+                    .is_some_and(|v| v == "Thu, 01 Oct 2026 10:00:00 GMT")
+            })
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let previous = vec![GithubRepo {
+            name: "app".to_string(),
+            full_name: "octocat/app".to_string(),
+            description: None,
+            html_url: String::new(),
+            stargazers_count: 7,
+            forks_count: 0,
+            watchers_count: 7,
+            language: None,
+            updated_at: None,
+            pushed_at: None,
+            open_issues_count: 0,
+            fork: false,
+            default_branch: None,
+            archived: false,
+        }];
+        let mut validators = BTreeMap::from([(
+            "/users/octocat/repos?per_page=100&page=1".to_string(),
+            Validators {
+                etag: None,
+                last_modified: Some("Thu, 01 Oct 2026 10:00:00 GMT".to_string()),
+            },
+        )]);
+
+        let (repos, truncated) = client
+            .fetch_repos("octocat", AccountKind::User, &previous, &mut validators)
+            .await
+            .unwrap();
+        assert!(!truncated);
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].stargazers_count, 7);
+    }
+
+    #[tokio::test]
+    async fn test_workflow_runs_cover_all_repos() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let run = |id: u64, repo: &str| {
+            serde_json::json!({"workflow_runs": [{
+                "id": id, "name": "CI", "workflow_id": 1, "head_branch": "main",
+                "status": "completed", "conclusion": "failure",
+                "created_at": "2026-10-01T10:00:00Z", "run_started_at": "2026-10-01T10:00:00Z",
+                "updated_at": "2026-10-01T10:05:00Z", "html_url": "",
+                "repository": {"full_name": repo},
+            }]})
+        };
+        Mock::given(method("GET"))
+            .and(path("/repos/octocat/quiet/actions/runs"))
+            .and(header("if-none-match", "\"quiet-1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octocat/quiet/actions/runs"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"quiet-1\"")
+                    .set_body_json(run(1, "octocat/quiet")),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octocat/busy/actions/runs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(run(2, "octocat/busy")))
+            .mount(&server)
+            .await;
+
+        let repo = |name: &str, fork: bool, archived: bool| GithubRepo {
+            name: name.to_string(),
+            full_name: format!("octocat/{}", name),
+            description: None,
+            html_url: String::new(),
+            stargazers_count: 0,
+            forks_count: 0,
+            watchers_count: 0,
+            language: None,
+            updated_at: None,
+            pushed_at: None,
+            open_issues_count: 0,
+            fork,
+            default_branch: Some("main".to_string()),
+            archived,
+        };
+        let client = mock_client(&server);
+        let mut state = GithubState {
+            repos: vec![
+                repo("quiet", false, false),
+                repo("busy", false, false),
+                repo("forked", true, false),
+                repo("old", false, true),
+            ],
+            ..Default::default()
+        };
+
+        // Every non-fork, non-archived repo is covered
+        let repos = client.actions_repos(&state.repos);
+        assert_eq!(repos.len(), 2);
+        client.fetch_workflow_runs(&repos, &mut state).await;
+        assert_eq!(state.workflow_runs.len(), 2);
+
+        // An unchanged repo answers 304 and keeps its runs
+        client.fetch_workflow_runs(&repos, &mut state).await;
+        let mut ids: Vec<_> = state.workflow_runs.iter().map(|r| r.id).collect();
+        ids.sort();
+        assert_eq!(ids, [1, 2]);
+        assert!(state.workflow_runs.iter().all(|r| r.is_broken_default()));
+    }
+}
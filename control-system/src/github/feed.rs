@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use super::models::{GithubEvent, GithubEventType};
+
+/// Groups of event types the received feed can be narrowed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Push,
+    /// Pull requests and reviews
+    Pr,
+    /// Issues and comments
+    Issue,
+    Release,
+    Star,
+    Fork,
+    /// Branch, tag and repository creation/deletion
+    Branch,
+}
+
+impl EventKind {
+    /// Order cycled through from the keyboard
+    const ALL: [EventKind; 7] = [
+        EventKind::Push,
+        EventKind::Pr,
+        EventKind::Issue,
+        EventKind::Release,
+        EventKind::Star,
+        EventKind::Fork,
+        EventKind::Branch,
+    ];
+
+    pub fn matches(&self, event_type: &GithubEventType) -> bool {
+        use GithubEventType as T;
+        matches!(
+            (self, event_type),
+            (Self::Push, T::PushEvent)
+                | (Self::Pr, T::PullRequestEvent | T::PullRequestReviewEvent)
+                | (Self::Issue, T::IssuesEvent | T::IssueCommentEvent)
+                | (Self::Release, T::ReleaseEvent)
+                | (Self::Star, T::WatchEvent)
+                | (Self::Fork, T::ForkEvent)
+                | (Self::Branch, T::CreateEvent | T::DeleteEvent)
+        )
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Push => "pushes",
+            Self::Pr => "PRs",
+            Self::Issue => "issues",
+            Self::Release => "releases",
+            Self::Star => "stars",
+            Self::Fork => "forks",
+            Self::Branch => "branches",
+        }
+    }
+
+    /// Next filter in `allowed` (all kinds when empty), wrapping back to no
+    /// filter after the last one
+    pub fn cycle(current: Option<EventKind>, allowed: &[EventKind]) -> Option<EventKind> {
+        let kinds: &[EventKind] = if allowed.is_empty() {
+            &Self::ALL
+        } else {
+            allowed
+        };
+        match current {
+            None => kinds.first().copied(),
+            Some(kind) => kinds
+                .iter()
+                .position(|k| *k == kind)
+                .and_then(|i| kinds.get(i + 1))
+                .copied(),
+        }
+    }
+}
+
+/// Settings for the feed of what followed users and watched repos are doing
+/// (persisted in settings.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedSettings {
+    /// Fetch `/users/{user}/received_events` (one extra request per refresh)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Kinds of events to show; empty shows everything
+    #[serde(default)]
+    pub types: Vec<EventKind>,
+    /// Repos (`owner/name`) whose events are hidden
+    #[serde(default)]
+    pub muted_repos: BTreeSet<String>,
+    /// Users whose events are hidden
+    #[serde(default)]
+    pub muted_actors: BTreeSet<String>,
+}
+
+impl FeedSettings {
+    /// Whether an event passes the configured types, the mutes and the
+    /// optional single-kind filter picked from the keyboard
+    pub fn allows(&self, event: &GithubEvent, kind: Option<EventKind>) -> bool {
+        let type_ok =
+            self.types.is_empty() || self.types.iter().any(|k| k.matches(&event.event_type));
+        let kind_ok = kind.is_none_or(|k| k.matches(&event.event_type));
+        let actor_muted = event
+            .actor
+            .as_ref()
+            .is_some_and(|a| self.muted_actors.iter().any(|m| m.eq_ignore_ascii_case(a)));
+        let repo_muted = self
+            .muted_repos
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(&event.repo_name));
+        type_ok && kind_ok && !actor_muted && !repo_muted
+    }
+
+    /// Events from `events` that should be shown, in their original order
+    pub fn filter<'a>(
+        &self,
+        events: &'a [GithubEvent],
+        kind: Option<EventKind>,
+    ) -> Vec<&'a GithubEvent> {
+        events.iter().filter(|e| self.allows(e, kind)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::EventPayload;
+
+    fn event(event_type: GithubEventType, repo: &str, actor: &str) -> GithubEvent {
+        GithubEvent {
+            id: format!("{}:{}", repo, actor),
+            event_type,
+            repo_name: repo.to_string(),
+            created_at: chrono::Utc::now(),
+            is_new: false,
+            actor: Some(actor.to_string()),
+            payload: EventPayload::None,
+        }
+    }
+
+    #[test]
+    fn test_filter_and_mutes() {
+        let events = vec![
+            event(GithubEventType::PushEvent, "rust-lang/rust", "alice"),
+            event(GithubEventType::ReleaseEvent, "tokio-rs/tokio", "bob"),
+            event(GithubEventType::WatchEvent, "noisy/bot", "carol"),
+            event(GithubEventType::PushEvent, "tokio-rs/tokio", "dependabot"),
+        ];
+        let mut settings = FeedSettings {
+            enabled: true,
+            muted_repos: BTreeSet::from(["Noisy/Bot".to_string()]),
+            muted_actors: BTreeSet::from(["dependabot".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(settings.filter(&events, None).len(), 2);
+        assert_eq!(settings.filter(&events, Some(EventKind::Release)).len(), 1);
+
+        settings.types = vec![EventKind::Push];
+        let shown = settings.filter(&events, None);
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].actor.as_deref(), Some("alice"));
+
+        // Cycling stays within the configured kinds, then clears the filter
+        assert_eq!(
+            EventKind::cycle(None, &settings.types),
+            Some(EventKind::Push)
+        );
+        assert_eq!(
+            EventKind::cycle(Some(EventKind::Push), &settings.types),
+            None
+        );
+        assert_eq!(EventKind::cycle(Some(EventKind::Branch), &[]), None);
+    }
+}
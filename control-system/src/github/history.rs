@@ -0,0 +1,219 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{debug, info};
+
+use super::models::GithubState;
+use crate::store::{Section, Store};
+
+/// Samples newer than this are kept at hourly resolution
+const HOURLY_WINDOW_DAYS: i64 = 7;
+/// Samples older than this are dropped
+const RETENTION_DAYS: i64 = 90;
+
+/// Totals captured after a successful refresh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistorySample {
+    pub at: DateTime<Utc>,
+    pub stars: u32,
+    pub forks: u32,
+    pub followers: u32,
+    /// Stars per repo, keyed by full name
+    #[serde(default)]
+    pub repo_stars: BTreeMap<String, u32>,
+}
+
+impl HistorySample {
+    /// Snapshot the current totals, or None if the profile hasn't loaded yet
+    pub fn from_state(state: &GithubState, at: DateTime<Utc>) -> Option<Self> {
+        let profile = state.profile.as_ref()?;
+        Some(Self {
+            at,
+            stars: state.stats.total_stars,
+            forks: state.stats.total_forks,
+            followers: profile.followers,
+            repo_stars: state
+                .repos
+                .iter()
+                .map(|r| (r.full_name.clone(), r.stargazers_count))
+                .collect(),
+        })
+    }
+}
+
+/// Star, fork and follower history, oldest sample first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    pub samples: Vec<HistorySample>,
+}
+
+impl History {
+    /// Add a sample. Within the same hour the newest sample replaces the
+    /// previous one; older samples are compacted to one per day.
+    pub fn record(&mut self, sample: HistorySample) {
+        let hour = |t: DateTime<Utc>| t.timestamp() / 3600;
+        if let Some(last) = self.samples.last_mut() {
+            if hour(last.at) == hour(sample.at) {
+                *last = sample;
+                return;
+            }
+        }
+        let now = sample.at;
+        self.samples.push(sample);
+        self.compact(now);
+    }
+
+    /// Keep the last sample of each day outside the hourly window and drop
+    /// anything past retention
+    fn compact(&mut self, now: DateTime<Utc>) {
+        let hourly_cutoff = now - Duration::days(HOURLY_WINDOW_DAYS);
+        let retention_cutoff = now - Duration::days(RETENTION_DAYS);
+
+        let samples = std::mem::take(&mut self.samples);
+        let mut kept: Vec<HistorySample> = Vec::with_capacity(samples.len());
+        for sample in samples {
+            if sample.at < retention_cutoff {
+                continue;
+            }
+            if let Some(prev) = kept.last_mut() {
+                if sample.at < hourly_cutoff && prev.at.date_naive() == sample.at.date_naive() {
+                    *prev = sample;
+                    continue;
+                }
+            }
+            kept.push(sample);
+        }
+        self.samples = kept;
+    }
+
+    /// The newest sample taken at or before `at`, falling back to the oldest
+    fn sample_at(&self, at: DateTime<Utc>) -> Option<&HistorySample> {
+        self.samples
+            .iter()
+            .rev()
+            .find(|s| s.at <= at)
+            .or(self.samples.first())
+    }
+
+    /// Change in a metric since `since`, e.g. stars gained this week
+    pub fn delta(&self, since: DateTime<Utc>, metric: impl Fn(&HistorySample) -> u32) -> i64 {
+        match (self.sample_at(since), self.samples.last()) {
+            (Some(then), Some(now)) => metric(now) as i64 - metric(then) as i64,
+            _ => 0,
+        }
+    }
+
+    /// Change in one repo's stars since `since`. None when the repo isn't
+    /// in both samples (e.g. created or first tracked since), so its stars
+    /// don't read as all gained at once.
+    pub fn repo_delta(&self, repo: &str, since: DateTime<Utc>) -> Option<i64> {
+        let then = self.sample_at(since)?.repo_stars.get(repo)?;
+        let now = self.samples.last()?.repo_stars.get(repo)?;
+        Some(*now as i64 - *then as i64)
+    }
+
+    /// One value per day for the last `days` days (oldest first), carrying
+    /// the previous value across days without samples
+    pub fn daily_series(
+        &self,
+        now: DateTime<Utc>,
+        days: usize,
+        metric: impl Fn(&HistorySample) -> u32,
+    ) -> Vec<u64> {
+        if self.samples.is_empty() {
+            return Vec::new();
+        }
+        (0..days)
+            .rev()
+            .map(|ago| {
+                let end_of_day = now - Duration::days(ago as i64);
+                self.sample_at(end_of_day).map_or(0, |s| metric(s) as u64)
+            })
+            .collect()
+    }
+}
+
+/// Persists [`History`] in its own store section
+pub struct HistoryStore {
+    store: Store,
+}
+
+impl HistoryStore {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Load history from disk (empty if missing)
+    pub async fn load(&self) -> Result<History> {
+        let history: History = self
+            .store
+            .load_json(Section::History)
+            .await?
+            .unwrap_or_default();
+        info!("Loaded {} history samples", history.samples.len());
+        Ok(history)
+    }
+
+    /// Save history to disk
+    pub async fn save(&self, history: &History) -> Result<()> {
+        self.store.save_json(Section::History, history).await?;
+        debug!("Saved {} history samples", history.samples.len());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(at: DateTime<Utc>, stars: u32) -> HistorySample {
+        HistorySample {
+            at,
+            stars,
+            forks: 0,
+            followers: 0,
+            repo_stars: BTreeMap::from([("me/app".to_string(), stars)]),
+        }
+    }
+
+    #[test]
+    fn test_record_and_compact() {
+        let start = DateTime::parse_from_rfc3339("2026-01-01T00:10:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut history = History::default();
+
+        // Same hour: replaced, not appended
+        history.record(sample(start, 1));
+        history.record(sample(start + Duration::minutes(5), 2));
+        assert_eq!(history.samples.len(), 1);
+        assert_eq!(history.samples[0].stars, 2);
+
+        // Hourly samples for 100 days
+        for h in 1..(100 * 24) {
+            history.record(sample(start + Duration::hours(h), 2 + h as u32 / 24));
+        }
+        let now = history.samples.last().unwrap().at;
+        assert!(history.samples[0].at >= now - Duration::days(RETENTION_DAYS));
+        // One sample per day outside the week of hourly ones
+        assert!(
+            history.samples.len() <= (RETENTION_DAYS - HOURLY_WINDOW_DAYS + 2) as usize + 7 * 24
+        );
+
+        assert_eq!(history.delta(now - Duration::days(7), |s| s.stars), 7);
+        assert_eq!(
+            history.repo_delta("me/app", now - Duration::days(1)),
+            Some(1)
+        );
+
+        // A repo that appeared since has no baseline to compare against
+        let mut newer = sample(now + Duration::hours(1), 200);
+        newer.repo_stars.insert("me/new".to_string(), 50);
+        history.record(newer);
+        assert_eq!(history.repo_delta("me/new", now - Duration::days(7)), None);
+        let series = history.daily_series(now, 5, |s| s.stars);
+        assert_eq!(series.len(), 5);
+        assert!(series.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
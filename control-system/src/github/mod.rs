@@ -1,7 +1,12 @@
 pub mod cache;
 pub mod client;
+pub mod feed;
+pub mod history;
 pub mod models;
 pub mod poller;
+pub mod repo_query;
 
+pub use feed::{EventKind, FeedSettings};
 pub use models::*;
 pub use poller::{GithubCommand, GithubPoller};
+pub use repo_query::{RepoColumn, RepoQuery};
@@ -1,5 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::history::History;
 
 /// GitHub user profile information
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -30,8 +33,152 @@ pub struct GithubRepo {
     pub pushed_at: Option<DateTime<Utc>>,
     pub open_issues_count: u32,
     pub fork: bool,
+    /// Default branch, used to tell broken main builds apart from branch runs
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// A commit on a repo's default branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitSummary {
+    pub sha: String,
+    /// First line of the commit message
+    pub message: String,
+    pub author: String,
+    pub date: Option<DateTime<Utc>>,
+}
+
+/// A repo's latest published release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseSummary {
+    pub tag: String,
+    pub name: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Latest release of a watched third-party repo, or its newest tag when it
+/// doesn't publish releases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedRelease {
+    /// `owner/name`
+    pub repo: String,
+    pub release: ReleaseSummary,
+    /// Taken from the tag list because the repo has no releases
+    pub is_tag: bool,
+    /// False for a release that appeared since the last acknowledgement
+    pub acknowledged: bool,
+}
+
+/// One day of repo traffic
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrafficDay {
+    pub date: NaiveDate,
+    pub count: u32,
+    pub uniques: u32,
+}
+
+/// Views and clones over the last 14 days (needs push access)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoTraffic {
+    pub views: Vec<TrafficDay>,
+    pub clones: Vec<TrafficDay>,
+}
+
+impl RepoTraffic {
+    /// `(date, views, clones)` for every day from the first to the last one
+    /// reported by either series, with days GitHub left out counted as 0
+    pub fn daily_counts(&self) -> Vec<(NaiveDate, u32, u32)> {
+        let dates = self.views.iter().chain(&self.clones).map(|d| d.date);
+        let (Some(first), Some(last)) = (dates.clone().min(), dates.max()) else {
+            return Vec::new();
+        };
+        let count = |days: &[TrafficDay], date: NaiveDate| {
+            days.iter().find(|d| d.date == date).map_or(0, |d| d.count)
+        };
+        first
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .map(|date| (date, count(&self.views, date), count(&self.clones, date)))
+            .collect()
+    }
+}
+
+/// Everything shown on the repository detail page, fetched on demand
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoDetail {
+    pub full_name: String,
+    /// Bytes of code per language, largest first
+    pub languages: Vec<(String, u64)>,
+    /// Recent commits on the default branch, newest first
+    pub commits: Vec<CommitSummary>,
+    /// None when the search for open PRs failed
+    pub open_prs: Option<u32>,
+    /// Open issues, not counting PRs
+    pub open_issues: u32,
+    pub latest_release: Option<ReleaseSummary>,
+    pub topics: Vec<String>,
+    pub license: Option<String>,
+    /// None without push access
+    pub traffic: Option<RepoTraffic>,
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// Set when the repo itself couldn't be loaded
+    pub error: Option<String>,
+}
+
+impl RepoDetail {
+    /// Share of each language in percent, largest first
+    pub fn language_shares(&self) -> Vec<(&str, f64)> {
+        let total: u64 = self.languages.iter().map(|(_, bytes)| bytes).sum();
+        if total == 0 {
+            return Vec::new();
+        }
+        self.languages
+            .iter()
+            .map(|(name, bytes)| (name.as_str(), *bytes as f64 * 100.0 / total as f64))
+            .collect()
+    }
+
+    /// Whether this detail is old enough to fetch again
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.error.is_some()
+            || self
+                .fetched_at
+                .is_none_or(|t| now - t > chrono::Duration::minutes(REPO_DETAIL_TTL_MINS))
+    }
+}
+
+/// Languages aggregated across repos, largest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LanguageBreakdown {
+    pub languages: Vec<(String, u64)>,
+    /// Whether values are bytes of code rather than numbers of repos
+    pub by_bytes: bool,
+    /// Non-fork repos considered
+    pub repos: usize,
+    /// How many of them are counted (by bytes, only repos whose languages
+    /// were fetched)
+    pub repos_counted: usize,
+}
+
+impl LanguageBreakdown {
+    /// Share of each language in percent, largest first
+    pub fn shares(&self) -> Vec<(&str, f64)> {
+        let total: u64 = self.languages.iter().map(|(_, v)| v).sum();
+        if total == 0 {
+            return Vec::new();
+        }
+        self.languages
+            .iter()
+            .map(|(name, v)| (name.as_str(), *v as f64 * 100.0 / total as f64))
+            .collect()
+    }
 }
 
+/// How long a fetched repo detail is reused before fetching again
+const REPO_DETAIL_TTL_MINS: i64 = 10;
+
 /// GitHub event types we care about
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GithubEventType {
@@ -49,6 +196,8 @@ pub enum GithubEventType {
     MemberEvent,
     GollumEvent,
     CommitCommentEvent,
+    /// Someone followed the user (detected by the poller, not from the events API)
+    FollowEvent,
     Unknown(String),
 }
 
@@ -69,6 +218,7 @@ impl GithubEventType {
             "MemberEvent" => Self::MemberEvent,
             "GollumEvent" => Self::GollumEvent,
             "CommitCommentEvent" => Self::CommitCommentEvent,
+            "FollowEvent" => Self::FollowEvent,
             other => Self::Unknown(other.to_string()),
         }
     }
@@ -89,6 +239,7 @@ impl GithubEventType {
             Self::MemberEvent => "[&]",
             Self::GollumEvent => "[W]",
             Self::CommitCommentEvent => "[C]",
+            Self::FollowEvent => "[F]",
             Self::Unknown(_) => "[?]",
         }
     }
@@ -98,9 +249,9 @@ impl GithubEventType {
             Self::PushEvent => "pushed to",
             Self::CreateEvent => "created",
             Self::DeleteEvent => "deleted",
-            Self::IssuesEvent => "opened issue in",
+            Self::IssuesEvent => "updated issue in",
             Self::IssueCommentEvent => "commented on",
-            Self::PullRequestEvent => "opened PR in",
+            Self::PullRequestEvent => "updated PR in",
             Self::PullRequestReviewEvent => "reviewed PR in",
             Self::WatchEvent => "starred",
             Self::ForkEvent => "forked",
@@ -109,11 +260,40 @@ impl GithubEventType {
             Self::MemberEvent => "added member to",
             Self::GollumEvent => "updated wiki in",
             Self::CommitCommentEvent => "commented on commit in",
+            Self::FollowEvent => "followed you",
             Self::Unknown(_) => "did something in",
         }
     }
 }
 
+/// Details parsed from an event's payload, depending on its type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum EventPayload {
+    #[default]
+    None,
+    /// Pushed commits, with the first few commit message headlines
+    Push {
+        branch: String,
+        commits: u32,
+        messages: Vec<String>,
+    },
+    /// Created or deleted branch/tag ("repository" has no name)
+    Ref {
+        ref_type: String,
+        name: Option<String>,
+    },
+    /// Issue or PR activity: opened/closed/merged, a comment or a review
+    Issue {
+        action: String,
+        number: u64,
+        title: String,
+    },
+    Release {
+        action: String,
+        tag: String,
+    },
+}
+
 /// GitHub event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GithubEvent {
@@ -122,6 +302,131 @@ pub struct GithubEvent {
     pub repo_name: String,
     pub created_at: DateTime<Utc>,
     pub is_new: bool,
+    /// Who did it, for events that aren't the user's own (new stargazers/followers)
+    #[serde(default)]
+    pub actor: Option<String>,
+    #[serde(default)]
+    pub payload: EventPayload,
+}
+
+impl GithubEvent {
+    /// Feed entry for someone starring one of the user's repos
+    pub fn starred(login: &str, repo_name: &str, at: DateTime<Utc>) -> Self {
+        Self {
+            id: format!("star:{}:{}", repo_name, login),
+            event_type: GithubEventType::WatchEvent,
+            repo_name: repo_name.to_string(),
+            created_at: at,
+            is_new: true,
+            actor: Some(login.to_string()),
+            payload: EventPayload::None,
+        }
+    }
+
+    /// Feed entry for someone following the user
+    pub fn followed(login: &str, at: DateTime<Utc>) -> Self {
+        Self {
+            id: format!("follow:{}", login),
+            event_type: GithubEventType::FollowEvent,
+            repo_name: String::new(),
+            created_at: at,
+            is_new: true,
+            actor: Some(login.to_string()),
+            payload: EventPayload::None,
+        }
+    }
+
+    /// What happened, from the payload when we have one, e.g.
+    /// "pushed 3 commits to main in" or "merged PR #12 in"
+    pub fn description(&self) -> String {
+        let plural = |n: u32| if n == 1 { "" } else { "s" };
+        match (&self.event_type, &self.payload) {
+            // Newer payloads may leave out the commit count
+            (
+                _,
+                EventPayload::Push {
+                    branch, commits: 0, ..
+                },
+            ) => format!("pushed to {} in", branch),
+            (
+                _,
+                EventPayload::Push {
+                    branch, commits, ..
+                },
+            ) => {
+                format!(
+                    "pushed {} commit{} to {} in",
+                    commits,
+                    plural(*commits),
+                    branch
+                )
+            }
+            (
+                GithubEventType::CreateEvent,
+                EventPayload::Ref {
+                    ref_type,
+                    name: None,
+                },
+            ) => {
+                format!("created {}", ref_type)
+            }
+            (
+                GithubEventType::DeleteEvent,
+                EventPayload::Ref {
+                    ref_type,
+                    name: None,
+                },
+            ) => {
+                format!("deleted {}", ref_type)
+            }
+            (
+                event_type,
+                EventPayload::Ref {
+                    ref_type,
+                    name: Some(name),
+                },
+            ) => {
+                let verb = match event_type {
+                    GithubEventType::DeleteEvent => "deleted",
+                    _ => "created",
+                };
+                format!("{} {} {} in", verb, ref_type, name)
+            }
+            (event_type, EventPayload::Issue { action, number, .. }) => match event_type {
+                GithubEventType::IssuesEvent => format!("{} issue #{} in", action, number),
+                GithubEventType::PullRequestEvent => format!("{} PR #{} in", action, number),
+                GithubEventType::PullRequestReviewEvent => match action.as_str() {
+                    "approved" => format!("approved PR #{} in", number),
+                    "changes_requested" => format!("requested changes on PR #{} in", number),
+                    _ => format!("reviewed PR #{} in", number),
+                },
+                _ => format!("commented on #{} in", number),
+            },
+            (_, EventPayload::Release { action, tag }) => format!("{} release {} in", action, tag),
+            (event_type, _) => event_type.description().to_string(),
+        }
+    }
+
+    /// Extra lines worth showing under the event: commit messages or the
+    /// issue/PR title
+    pub fn detail_lines(&self) -> Vec<&str> {
+        match &self.payload {
+            EventPayload::Push { messages, .. } => messages.iter().map(String::as_str).collect(),
+            EventPayload::Issue { title, .. } if !title.is_empty() => vec![title.as_str()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// One-line summary, e.g. "alice starred repo-x"
+    pub fn summary(&self) -> String {
+        let repo_short = self.repo_name.rsplit('/').next().unwrap_or(&self.repo_name);
+        match &self.actor {
+            Some(actor) => format!("{} {} {}", actor, self.description(), repo_short)
+                .trim_end()
+                .to_string(),
+            None => format!("{} {}", self.description(), repo_short),
+        }
+    }
 }
 
 /// Rate limit information
@@ -154,6 +459,304 @@ pub struct GithubStats {
     pub total_watchers: u32,
 }
 
+impl GithubStats {
+    /// Totals over a set of repos
+    pub fn from_repos<'a>(repos: impl IntoIterator<Item = &'a GithubRepo>) -> Self {
+        repos.into_iter().fold(Self::default(), |mut stats, r| {
+            stats.total_stars += r.stargazers_count;
+            stats.total_forks += r.forks_count;
+            stats.total_repos += 1;
+            stats.total_watchers += r.watchers_count;
+            stats
+        })
+    }
+}
+
+/// Whether a tracked account is a user or an organization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AccountKind {
+    #[default]
+    User,
+    Organization,
+}
+
+/// A tracked user or organization with its own profile and repos
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubAccount {
+    pub login: String,
+    pub kind: AccountKind,
+    pub profile: Option<GithubProfile>,
+    pub repos: Vec<GithubRepo>,
+    /// Whether repos stopped at the page limit, leaving some out
+    #[serde(default)]
+    pub repos_truncated: bool,
+    pub stats: GithubStats,
+}
+
+/// One day of the contribution calendar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContributionDay {
+    pub date: NaiveDate,
+    pub count: u32,
+    /// Intensity bucket as shown on github.com (0 = none, 4 = most)
+    pub level: u8,
+}
+
+/// The user's contribution calendar for the last year (oldest day first)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ContributionCalendar {
+    pub total: u32,
+    pub days: Vec<ContributionDay>,
+}
+
+impl ContributionCalendar {
+    /// Map a GraphQL `ContributionLevel` to a 0-4 bucket
+    pub fn level_from_str(s: &str) -> u8 {
+        match s {
+            "FIRST_QUARTILE" => 1,
+            "SECOND_QUARTILE" => 2,
+            "THIRD_QUARTILE" => 3,
+            "FOURTH_QUARTILE" => 4,
+            _ => 0,
+        }
+    }
+
+    /// Consecutive days with contributions ending today. A streak is still
+    /// alive if today has no contributions yet but yesterday did.
+    pub fn current_streak(&self, today: NaiveDate) -> u32 {
+        let mut days = self
+            .days
+            .iter()
+            .rev()
+            .skip_while(|d| d.date > today)
+            .peekable();
+        if days.peek().is_some_and(|d| d.date == today && d.count == 0) {
+            days.next();
+        }
+        days.take_while(|d| d.count > 0).count() as u32
+    }
+
+    /// Longest run of consecutive days with contributions
+    pub fn longest_streak(&self) -> u32 {
+        let mut longest = 0;
+        let mut run = 0;
+        for day in &self.days {
+            run = if day.count > 0 { run + 1 } else { 0 };
+            longest = longest.max(run);
+        }
+        longest
+    }
+}
+
+/// Which inbox section an item belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InboxKind {
+    /// Open pull request authored by the user
+    AuthoredPr,
+    /// Open pull request waiting for the user's review
+    ReviewRequested,
+    /// Open issue assigned to the user
+    AssignedIssue,
+}
+
+impl InboxKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            InboxKind::AuthoredPr => "Your Pull Requests",
+            InboxKind::ReviewRequested => "Review Requested",
+            InboxKind::AssignedIssue => "Assigned Issues",
+        }
+    }
+}
+
+/// Combined CI state of a pull request's head commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CiStatus {
+    Success,
+    Failure,
+    Pending,
+}
+
+impl CiStatus {
+    /// Map a GraphQL `StatusState`
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "SUCCESS" => Some(Self::Success),
+            "FAILURE" | "ERROR" => Some(Self::Failure),
+            "PENDING" | "EXPECTED" => Some(Self::Pending),
+            _ => None,
+        }
+    }
+}
+
+/// Review decision on a pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+impl ReviewState {
+    /// Map a GraphQL `PullRequestReviewDecision`
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "APPROVED" => Some(Self::Approved),
+            "CHANGES_REQUESTED" => Some(Self::ChangesRequested),
+            "REVIEW_REQUIRED" => Some(Self::ReviewRequired),
+            _ => None,
+        }
+    }
+}
+
+/// An open pull request or issue that needs the user's attention
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxItem {
+    pub kind: InboxKind,
+    pub repo_name: String,
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub labels: Vec<String>,
+    pub is_draft: bool,
+    /// CI rollup (pull requests only)
+    pub ci: Option<CiStatus>,
+    /// Review decision (pull requests only)
+    pub review: Option<ReviewState>,
+}
+
+/// Why the user received a notification, grouped the way the inbox shows them
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum NotificationReason {
+    ReviewRequested,
+    Mention,
+    CiActivity,
+    Assigned,
+    /// Any other reason (comment, author, subscribed, ...)
+    Other(String),
+}
+
+impl NotificationReason {
+    /// Map the API `reason` field
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "review_requested" => Self::ReviewRequested,
+            "mention" | "team_mention" => Self::Mention,
+            "ci_activity" => Self::CiActivity,
+            "assign" => Self::Assigned,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Group header in the notifications widget
+    pub fn heading(&self) -> String {
+        match self {
+            Self::ReviewRequested => "Review requested".to_string(),
+            Self::Mention => "Mentioned".to_string(),
+            Self::CiActivity => "CI activity".to_string(),
+            Self::Assigned => "Assigned".to_string(),
+            Self::Other(reason) => {
+                let reason = reason.replace('_', " ");
+                let mut chars = reason.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => reason,
+                }
+            }
+        }
+    }
+}
+
+/// An unread notification thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    /// Thread id, used to mark it as read
+    pub id: String,
+    pub repo_name: String,
+    pub reason: NotificationReason,
+    pub title: String,
+    /// Subject type, e.g. "PullRequest", "Issue", "CheckSuite"
+    pub subject_type: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome of a GitHub Actions workflow run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunState {
+    Queued,
+    Running,
+    Success,
+    Failure,
+    Cancelled,
+    Skipped,
+}
+
+impl RunState {
+    /// Map the REST `status` and `conclusion` fields
+    pub fn parse(status: &str, conclusion: Option<&str>) -> Self {
+        match (status, conclusion) {
+            ("completed", Some("success")) => Self::Success,
+            ("completed", Some("cancelled")) => Self::Cancelled,
+            ("completed", Some("skipped" | "neutral" | "stale")) => Self::Skipped,
+            ("completed", _) => Self::Failure,
+            ("in_progress", _) => Self::Running,
+            _ => Self::Queued,
+        }
+    }
+}
+
+/// The latest run of a workflow on one branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub repo_name: String,
+    pub workflow_name: String,
+    pub branch: String,
+    pub state: RunState,
+    pub url: String,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Whether the run is on the repository's default branch
+    pub on_default_branch: bool,
+}
+
+impl WorkflowRun {
+    /// Wall-clock duration; still-running runs are measured up to `now`
+    pub fn duration(&self, now: DateTime<Utc>) -> chrono::Duration {
+        let end = match self.state {
+            RunState::Queued | RunState::Running => now,
+            _ => self.updated_at,
+        };
+        (end - self.started_at).max(chrono::Duration::zero())
+    }
+
+    /// A failed run on the default branch, i.e. a broken main build
+    pub fn is_broken_default(&self) -> bool {
+        self.on_default_branch && self.state == RunState::Failure
+    }
+}
+
+/// Conditional request validators saved from an endpoint's last 200 response
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// What the last refresh learned about how soon to poll again
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PollHints {
+    /// `X-Poll-Interval` from the events endpoint, in seconds
+    pub poll_interval_secs: Option<u64>,
+    /// The server asked us to slow down (secondary rate limit or 5xx)
+    pub throttled: bool,
+    /// `Retry-After` from a throttled response, in seconds
+    pub retry_after_secs: Option<u64>,
+}
+
 /// Status of GitHub data fetching
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum FetchStatus {
@@ -177,38 +780,164 @@ impl FetchStatus {
 /// Complete GitHub state
 #[derive(Debug, Clone, Default)]
 pub struct GithubState {
+    /// Profile of the primary (configured) user
     pub profile: Option<GithubProfile>,
+    /// Repos of every tracked account
     pub repos: Vec<GithubRepo>,
+    /// Tracked accounts, primary user first
+    pub accounts: Vec<GithubAccount>,
     pub events: Vec<GithubEvent>,
     pub stats: GithubStats,
     pub rate_limit: RateLimit,
     pub last_updated: Option<DateTime<Utc>>,
     pub status: FetchStatus,
+    /// Contribution calendar (only fetched with a token)
+    pub contributions: Option<ContributionCalendar>,
+    /// Open PRs and issues needing attention (only fetched with a token)
+    pub inbox: Vec<InboxItem>,
+    /// Unread notification threads (only fetched with a token)
+    pub notifications: Vec<Notification>,
+    /// Latest Actions runs per workflow and branch (only fetched with a token)
+    pub workflow_runs: Vec<WorkflowRun>,
+    /// Star/fork/follower samples (persisted separately from the cache)
+    pub history: History,
+    /// New stargazer/follower entries for the feed, newest first
+    pub announcements: Vec<GithubEvent>,
+    /// What followed users and watched repos are doing, newest first
+    pub received_events: Vec<GithubEvent>,
+    /// Repo detail pages fetched this session, by full name
+    pub repo_details: BTreeMap<String, RepoDetail>,
+    /// Bytes per language for each repo (`owner/name`), when enabled
+    pub language_bytes: BTreeMap<String, Vec<(String, u64)>>,
+    /// Latest releases of the watched repos, in watchlist order
+    pub watched_releases: Vec<WatchedRelease>,
+    /// Followers already seen, so nobody is announced twice
+    pub known_followers: BTreeSet<String>,
+    /// Stargazers already seen per repo (full name)
+    pub known_stargazers: BTreeMap<String, BTreeSet<String>>,
+    /// ETag/Last-Modified per endpoint URL, sent back as conditional headers
+    pub validators: BTreeMap<String, Validators>,
+    /// Scheduling hints from the last refresh
+    pub poll_hints: PollHints,
+    /// When the poller will refresh next
+    pub next_refresh_at: Option<DateTime<Utc>>,
 }
 
 impl GithubState {
-    /// Get top N repos by star count
-    pub fn top_repos_by_stars(&self, n: usize) -> Vec<&GithubRepo> {
-        let mut repos: Vec<_> = self.repos.iter().filter(|r| !r.fork).collect();
-        repos.sort_by(|a, b| b.stargazers_count.cmp(&a.stargazers_count));
-        repos.into_iter().take(n).collect()
+    /// The selected account, or None for all accounts
+    pub fn account(&self, selected: Option<usize>) -> Option<&GithubAccount> {
+        selected.and_then(|i| self.accounts.get(i))
+    }
+
+    /// Whether the selected account's repos (or any account's, for all of
+    /// them) were cut short by the page limit
+    pub fn repos_truncated(&self, selected: Option<usize>) -> bool {
+        match self.account(selected) {
+            Some(account) => account.repos_truncated,
+            None => self.accounts.iter().any(|a| a.repos_truncated),
+        }
+    }
+
+    /// Repos of the selected account, or of every account
+    pub fn account_repos(&self, selected: Option<usize>) -> &[GithubRepo] {
+        match self.account(selected) {
+            Some(account) => &account.repos,
+            None => &self.repos,
+        }
+    }
+
+    /// Languages across non-fork repos (for one account, or all): summed
+    /// bytes when per-repo byte counts were fetched, otherwise the number of
+    /// repos whose main language it is
+    pub fn language_breakdown(&self, account: Option<usize>) -> LanguageBreakdown {
+        let repos = self.account_repos(account).iter().filter(|r| !r.fork);
+        let by_bytes = !self.language_bytes.is_empty();
+        let mut totals: BTreeMap<&str, u64> = BTreeMap::new();
+        let (mut repo_count, mut repos_counted) = (0, 0);
+        for repo in repos {
+            repo_count += 1;
+            if by_bytes {
+                // Only the most recently pushed repos have byte counts
+                let Some(languages) = self.language_bytes.get(&repo.full_name) else {
+                    continue;
+                };
+                repos_counted += 1;
+                for (name, bytes) in languages {
+                    *totals.entry(name.as_str()).or_default() += bytes;
+                }
+            } else if let Some(ref language) = repo.language {
+                repos_counted += 1;
+                *totals.entry(language.as_str()).or_default() += 1;
+            }
+        }
+        let mut languages: Vec<(String, u64)> = totals
+            .into_iter()
+            .filter(|(_, v)| *v > 0)
+            .map(|(name, v)| (name.to_string(), v))
+            .collect();
+        languages.sort_by_key(|(_, v)| std::cmp::Reverse(*v));
+        LanguageBreakdown {
+            languages,
+            by_bytes,
+            repos: repo_count,
+            repos_counted,
+        }
+    }
+
+    /// Watched releases not yet acknowledged
+    pub fn new_release_count(&self) -> usize {
+        self.watched_releases
+            .iter()
+            .filter(|r| !r.acknowledged)
+            .count()
+    }
+
+    /// Inbox items of one kind, most recently updated first
+    pub fn inbox_items(&self, kind: InboxKind) -> Vec<&InboxItem> {
+        let mut items: Vec<_> = self.inbox.iter().filter(|i| i.kind == kind).collect();
+        items.sort_by_key(|i| std::cmp::Reverse(i.updated_at));
+        items
+    }
+
+    /// Unread notifications in display order: grouped by repo, then reason,
+    /// newest first within a group
+    pub fn notification_threads(&self) -> Vec<&Notification> {
+        let mut threads: Vec<_> = self.notifications.iter().collect();
+        threads.sort_by(|a, b| {
+            a.repo_name
+                .cmp(&b.repo_name)
+                .then_with(|| a.reason.cmp(&b.reason))
+                .then_with(|| b.updated_at.cmp(&a.updated_at))
+        });
+        threads
+    }
+
+    /// Activity feed: fetched events plus stargazer/follower announcements,
+    /// newest first
+    pub fn feed_events(&self) -> Vec<&GithubEvent> {
+        let mut events: Vec<_> = self.events.iter().chain(&self.announcements).collect();
+        events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        events
     }
 
-    /// Get recently updated repos
-    pub fn recently_updated_repos(&self, n: usize) -> Vec<&GithubRepo> {
-        let mut repos: Vec<_> = self.repos.iter().collect();
-        repos.sort_by(|a, b| b.pushed_at.cmp(&a.pushed_at));
-        repos.into_iter().take(n).collect()
+    /// Workflow runs for the board: broken default-branch builds first,
+    /// then most recently updated
+    pub fn workflow_board(&self) -> Vec<&WorkflowRun> {
+        let mut runs: Vec<_> = self.workflow_runs.iter().collect();
+        runs.sort_by(|a, b| {
+            b.is_broken_default()
+                .cmp(&a.is_broken_default())
+                .then_with(|| b.updated_at.cmp(&a.updated_at))
+        });
+        runs
     }
 
-    /// Compute statistics from repos
+    /// Compute statistics from repos, overall and per account
     pub fn compute_stats(&mut self) {
-        self.stats = GithubStats {
-            total_stars: self.repos.iter().map(|r| r.stargazers_count).sum(),
-            total_forks: self.repos.iter().map(|r| r.forks_count).sum(),
-            total_repos: self.repos.len() as u32,
-            total_watchers: self.repos.iter().map(|r| r.watchers_count).sum(),
-        };
+        self.stats = GithubStats::from_repos(&self.repos);
+        for account in &mut self.accounts {
+            account.stats = GithubStats::from_repos(&account.repos);
+        }
     }
 }
 
@@ -228,6 +957,80 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_language_breakdown() {
+        let repo = |name: &str, language: Option<&str>, fork: bool| GithubRepo {
+            name: name.to_string(),
+            full_name: format!("me/{}", name),
+            description: None,
+            html_url: String::new(),
+            stargazers_count: 0,
+            forks_count: 0,
+            watchers_count: 0,
+            language: language.map(|l| l.to_string()),
+            updated_at: None,
+            pushed_at: None,
+            open_issues_count: 0,
+            fork,
+            default_branch: None,
+            archived: false,
+        };
+        let mut state = GithubState {
+            repos: vec![
+                repo("a", Some("Rust"), false),
+                repo("b", Some("Rust"), false),
+                repo("c", Some("Go"), false),
+                repo("d", Some("Go"), true),
+                repo("e", None, false),
+            ],
+            ..Default::default()
+        };
+        let counts = state.language_breakdown(None);
+        assert!(!counts.by_bytes);
+        assert_eq!((counts.repos_counted, counts.repos), (3, 4));
+        assert_eq!(
+            counts.languages,
+            vec![("Rust".to_string(), 2), ("Go".to_string(), 1)]
+        );
+
+        state.language_bytes = BTreeMap::from([
+            ("me/a".to_string(), vec![("Rust".to_string(), 300)]),
+            (
+                "me/c".to_string(),
+                vec![("Go".to_string(), 600), ("Shell".to_string(), 100)],
+            ),
+            ("me/d".to_string(), vec![("Go".to_string(), 10_000)]),
+        ]);
+        let bytes = state.language_breakdown(None);
+        assert!(bytes.by_bytes);
+        assert_eq!(bytes.languages[0], ("Go".to_string(), 600));
+        assert_eq!(bytes.shares()[1], ("Rust", 30.0));
+        // b and e weren't among the repos whose bytes were fetched
+        assert_eq!((bytes.repos_counted, bytes.repos), (2, 4));
+    }
+
+    #[test]
+    fn test_traffic_shares_date_axis() {
+        let day = |d: u32, count: u32| TrafficDay {
+            date: NaiveDate::from_ymd_opt(2026, 10, d).unwrap(),
+            count,
+            uniques: count,
+        };
+        // Clones start later and views skip a quiet day
+        let traffic = RepoTraffic {
+            views: vec![day(10, 4), day(12, 6)],
+            clones: vec![day(11, 1), day(13, 2)],
+        };
+        let counts: Vec<(u32, u32)> = traffic
+            .daily_counts()
+            .iter()
+            .map(|(_, views, clones)| (*views, *clones))
+            .collect();
+        assert_eq!(counts, [(4, 0), (0, 1), (6, 0), (0, 2)]);
+        assert_eq!(traffic.daily_counts()[0].0, day(10, 0).date);
+        assert!(RepoTraffic::default().daily_counts().is_empty());
+    }
+
     #[test]
     fn test_rate_limit_percentage() {
         let rate_limit = RateLimit {
@@ -237,4 +1040,67 @@ mod tests {
         };
         assert!((rate_limit.usage_percentage() - 25.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_notification_grouping() {
+        let now = Utc::now();
+        let thread = |id: &str, repo: &str, reason: &str, age_mins: i64| Notification {
+            id: id.to_string(),
+            repo_name: repo.to_string(),
+            reason: NotificationReason::parse(reason),
+            title: String::new(),
+            subject_type: "PullRequest".to_string(),
+            updated_at: now - chrono::Duration::minutes(age_mins),
+        };
+        let state = GithubState {
+            notifications: vec![
+                thread("1", "b/repo", "mention", 5),
+                thread("2", "a/repo", "subscribed", 1),
+                thread("3", "a/repo", "review_requested", 10),
+                thread("4", "a/repo", "review_requested", 2),
+            ],
+            ..Default::default()
+        };
+        let ids: Vec<_> = state
+            .notification_threads()
+            .iter()
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["4", "3", "2", "1"]);
+        assert_eq!(
+            NotificationReason::parse("review_requested").heading(),
+            "Review requested"
+        );
+        assert_eq!(
+            NotificationReason::parse("security_alert").heading(),
+            "Security alert"
+        );
+    }
+
+    #[test]
+    fn test_contribution_streaks() {
+        let start = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let counts = [1, 2, 0, 3, 1, 4, 2, 0, 5, 1, 0];
+        let calendar = ContributionCalendar {
+            total: counts.iter().sum(),
+            days: counts
+                .iter()
+                .enumerate()
+                .map(|(i, &count)| ContributionDay {
+                    date: start + chrono::Duration::days(i as i64),
+                    count,
+                    level: 0,
+                })
+                .collect(),
+        };
+        let last = start + chrono::Duration::days(counts.len() as i64 - 1);
+
+        assert_eq!(calendar.longest_streak(), 4);
+        // Nothing yet today: the streak ending yesterday still counts
+        assert_eq!(calendar.current_streak(last), 2);
+        assert_eq!(calendar.current_streak(last - chrono::Duration::days(1)), 2);
+        assert_eq!(calendar.current_streak(last - chrono::Duration::days(3)), 4);
+        // Broken streak: no contributions yesterday or today
+        assert_eq!(calendar.current_streak(last + chrono::Duration::days(1)), 0);
+    }
 }
@@ -4,16 +4,103 @@ use tokio::sync::{mpsc, watch};
 use tracing::{debug, error, info};
 
 use crate::config::Config;
+use crate::store::Store;
+use crate::system::connectivity::{back_online, Connectivity};
 
 use super::cache::GithubCache;
 use super::client::GithubClient;
-use super::models::GithubState;
+use super::history::{HistorySample, HistoryStore};
+use super::models::{FetchStatus, GithubState, RateLimit, RepoDetail};
+
+/// Longest wait between refreshes while backing off
+const MAX_BACKOFF: Duration = Duration::from_secs(15 * 60);
+
+/// Requests assumed per refresh until one has been measured
+const DEFAULT_REFRESH_COST: u32 = 5;
+
+/// Slack added after the rate limit window resets
+const RESET_SLACK_SECS: u64 = 5;
+
+/// Decides when to refresh next from the remaining quota, `X-Poll-Interval`
+/// and whether the server is throttling us. `refresh_secs` is the floor.
+#[derive(Debug)]
+struct RefreshScheduler {
+    base: Duration,
+    /// Consecutive throttled refreshes, for exponential backoff
+    failures: u32,
+    /// Requests the last refresh used, measured from the quota drop
+    cost: u32,
+    /// Quota seen after the previous refresh
+    last_quota: Option<RateLimit>,
+}
+
+impl RefreshScheduler {
+    fn new(base: Duration) -> Self {
+        Self {
+            base,
+            failures: 0,
+            cost: DEFAULT_REFRESH_COST,
+            last_quota: None,
+        }
+    }
+
+    /// Delay until the next refresh after one that produced `state`
+    fn next_delay(&mut self, state: &GithubState, now: chrono::DateTime<chrono::Utc>) -> Duration {
+        let hints = &state.poll_hints;
+
+        // Back off exponentially while the server is throttling or erroring
+        if hints.throttled {
+            self.failures += 1;
+            let backoff = self
+                .base
+                .saturating_mul(1 << self.failures.min(6))
+                .min(MAX_BACKOFF);
+            let retry_after = Duration::from_secs(hints.retry_after_secs.unwrap_or(0));
+            return backoff.max(retry_after);
+        }
+        self.failures = 0;
+
+        // Measure what a refresh costs (304s are free) within one window
+        let quota = &state.rate_limit;
+        if let Some(last) = &self.last_quota {
+            if last.reset_at == quota.reset_at && last.remaining >= quota.remaining {
+                self.cost = (last.remaining - quota.remaining).max(1);
+            }
+        }
+        self.last_quota = Some(quota.clone());
+
+        let mut delay = self.base;
+        if let Some(secs) = hints.poll_interval_secs {
+            delay = delay.max(Duration::from_secs(secs));
+        }
+
+        // Spread the remaining quota evenly until the window resets
+        if let (true, Some(reset_at)) = (quota.limit > 0, quota.reset_at) {
+            let until_reset = (reset_at - now).num_seconds().max(0) as u64;
+            let spread = if quota.remaining < self.cost {
+                until_reset + RESET_SLACK_SECS
+            } else {
+                until_reset / (quota.remaining / self.cost) as u64
+            };
+            delay = delay.max(Duration::from_secs(spread));
+        }
+        delay
+    }
+}
 
 /// Commands that can be sent to the GitHub poller
 #[derive(Debug, Clone)]
 pub enum GithubCommand {
     /// Force an immediate refresh
     Refresh,
+    /// Mark one notification thread as read
+    MarkNotificationRead(String),
+    /// Mark every notification as read
+    MarkAllNotificationsRead,
+    /// Load the detail page for a repo (`owner/name`)
+    FetchRepoDetail(String),
+    /// Stop highlighting new watched releases
+    AcknowledgeReleases,
     /// Stop the poller
     Stop,
 }
@@ -22,6 +109,7 @@ pub enum GithubCommand {
 pub struct GithubPoller {
     client: Arc<GithubClient>,
     cache: Arc<GithubCache>,
+    history: Arc<HistoryStore>,
     refresh_interval: Duration,
 }
 
@@ -29,17 +117,29 @@ impl GithubPoller {
     /// Create a new GitHub poller
     pub fn new(config: &Config) -> anyhow::Result<Self> {
         let client = Arc::new(GithubClient::new(config)?);
-        let cache = Arc::new(GithubCache::new(&config.cache_path));
+        let store = Store::new(&config.store_dir);
+        let cache = Arc::new(GithubCache::new(store.clone()));
+        let history = Arc::new(HistoryStore::new(store));
 
         Ok(Self {
             client,
             cache,
+            history,
             refresh_interval: Duration::from_secs(config.refresh_secs),
         })
     }
 
     /// Load initial state from cache
     pub async fn load_cached_state(&self) -> GithubState {
+        let mut state = self.load_cache().await;
+        match self.history.load().await {
+            Ok(history) => state.history = history,
+            Err(e) => error!("Failed to load history: {}", e),
+        }
+        state
+    }
+
+    async fn load_cache(&self) -> GithubState {
         match self.cache.load().await {
             Ok(Some(data)) => {
                 info!("Loaded GitHub state from cache");
@@ -56,11 +156,13 @@ impl GithubPoller {
         }
     }
 
-    /// Start the poller task
+    /// Start the poller task. Scheduled refreshes are skipped while
+    /// `connectivity` is offline, and one runs as soon as it's back.
     /// Returns a watch receiver for state updates and an mpsc sender for commands
     pub fn start(
         self,
         initial_state: GithubState,
+        mut connectivity: watch::Receiver<Connectivity>,
     ) -> (
         watch::Receiver<GithubState>,
         mpsc::Sender<GithubCommand>,
@@ -70,39 +172,82 @@ impl GithubPoller {
 
         let client = self.client;
         let cache = self.cache;
+        let history = self.history;
         let refresh_interval = self.refresh_interval;
 
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(refresh_interval);
-            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
-            // Do an initial fetch
-            let current = state_tx.borrow().clone();
-            let new_state = client.fetch_all(&current).await;
-            let _ = state_tx.send(new_state.clone());
-            if let Err(e) = cache.save(&new_state).await {
-                error!("Failed to save cache: {}", e);
-            }
+            let mut scheduler = RefreshScheduler::new(refresh_interval);
+
+            // Do an initial fetch (or wait for the network)
+            let mut next_refresh = tokio::time::Instant::now();
 
             loop {
                 tokio::select! {
-                    _ = interval.tick() => {
-                        debug!("Periodic GitHub refresh triggered");
-                        let current = state_tx.borrow().clone();
-                        let new_state = client.fetch_all(&current).await;
-                        let _ = state_tx.send(new_state.clone());
-                        if let Err(e) = cache.save(&new_state).await {
-                            error!("Failed to save cache: {}", e);
+                    _ = tokio::time::sleep_until(next_refresh) => {
+                        if !connectivity.borrow().online {
+                            debug!("Offline, skipping GitHub refresh");
+                            next_refresh = tokio::time::Instant::now() + refresh_interval;
+                            continue;
                         }
+                        debug!("Scheduled GitHub refresh triggered");
+                        next_refresh =
+                            refresh(&client, &cache, &history, &state_tx, &mut scheduler).await;
+                    }
+                    _ = back_online(&mut connectivity) => {
+                        info!("Back online, refreshing GitHub");
+                        next_refresh =
+                            refresh(&client, &cache, &history, &state_tx, &mut scheduler).await;
                     }
                     Some(cmd) = cmd_rx.recv() => {
                         match cmd {
                             GithubCommand::Refresh => {
                                 info!("Manual GitHub refresh triggered");
-                                let current = state_tx.borrow().clone();
-                                let new_state = client.fetch_all(&current).await;
-                                let _ = state_tx.send(new_state.clone());
-                                if let Err(e) = cache.save(&new_state).await {
+                                next_refresh =
+                                    refresh(&client, &cache, &history, &state_tx, &mut scheduler).await;
+                            }
+                            GithubCommand::MarkNotificationRead(id) => {
+                                match client.mark_notification_read(&id).await {
+                                    Ok(()) => {
+                                        info!("Marked notification {} as read", id);
+                                        state_tx.send_modify(|s| s.notifications.retain(|n| n.id != id));
+                                    }
+                                    Err(e) => error!("Failed to mark notification {} as read: {}", id, e),
+                                }
+                            }
+                            GithubCommand::MarkAllNotificationsRead => {
+                                match client.mark_all_notifications_read().await {
+                                    Ok(()) => {
+                                        info!("Marked all notifications as read");
+                                        state_tx.send_modify(|s| s.notifications.clear());
+                                    }
+                                    Err(e) => error!("Failed to mark notifications as read: {}", e),
+                                }
+                            }
+                            GithubCommand::FetchRepoDetail(full_name) => {
+                                let detail = match client.fetch_repo_detail(&full_name).await {
+                                    Ok(detail) => detail,
+                                    Err(e) => {
+                                        error!("Failed to fetch details of {}: {}", full_name, e);
+                                        RepoDetail {
+                                            full_name: full_name.clone(),
+                                            error: Some(e.to_string()),
+                                            ..Default::default()
+                                        }
+                                    }
+                                };
+                                state_tx.send_modify(|s| {
+                                    s.repo_details.insert(full_name, detail);
+                                });
+                            }
+                            GithubCommand::AcknowledgeReleases => {
+                                state_tx.send_modify(|s| {
+                                    for release in &mut s.watched_releases {
+                                        release.acknowledged = true;
+                                    }
+                                });
+                                // Saved now so a restart doesn't bring them back
+                                let state = state_tx.borrow().clone();
+                                if let Err(e) = cache.save(&state).await {
                                     error!("Failed to save cache: {}", e);
                                 }
                             }
@@ -119,3 +264,98 @@ impl GithubPoller {
         (state_rx, cmd_tx)
     }
 }
+
+/// Fetch fresh data, record a history sample on success, publish and cache
+/// it. Returns when the next refresh is due.
+async fn refresh(
+    client: &GithubClient,
+    cache: &GithubCache,
+    history: &HistoryStore,
+    state_tx: &watch::Sender<GithubState>,
+    scheduler: &mut RefreshScheduler,
+) -> tokio::time::Instant {
+    let current = state_tx.borrow().clone();
+    let mut new_state = client.fetch_all(&current).await;
+
+    let now = chrono::Utc::now();
+    let delay = scheduler.next_delay(&new_state, now);
+    new_state.next_refresh_at = chrono::Duration::from_std(delay).ok().map(|d| now + d);
+    if delay > scheduler.base {
+        info!("Next GitHub refresh in {}s", delay.as_secs());
+    }
+
+    // Only sample complete fetches so a failed repo list doesn't read as lost stars
+    if new_state.status == FetchStatus::Success {
+        if let Some(sample) = HistorySample::from_state(&new_state, chrono::Utc::now()) {
+            new_state.history.record(sample);
+            if let Err(e) = history.save(&new_state.history).await {
+                error!("Failed to save history: {}", e);
+            }
+        }
+    }
+
+    let _ = state_tx.send(new_state.clone());
+    if let Err(e) = cache.save(&new_state).await {
+        error!("Failed to save cache: {}", e);
+    }
+    tokio::time::Instant::now() + delay
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::PollHints;
+    use chrono::Utc;
+
+    fn state(remaining: u32, reset_in_secs: i64, hints: PollHints) -> GithubState {
+        GithubState {
+            rate_limit: RateLimit {
+                limit: 5000,
+                remaining,
+                reset_at: Some(Utc::now() + chrono::Duration::seconds(reset_in_secs)),
+            },
+            poll_hints: hints,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_refresh_scheduling() {
+        let now = Utc::now();
+        let base = Duration::from_secs(60);
+
+        // Plenty of quota: the configured interval
+        let mut scheduler = RefreshScheduler::new(base);
+        assert_eq!(scheduler.next_delay(&state(5000, 3600, PollHints::default()), now), base);
+
+        // 10 refreshes' worth left for 50 minutes: one every 5 minutes
+        let mut scheduler = RefreshScheduler::new(base);
+        let delay = scheduler.next_delay(&state(50, 3000, PollHints::default()), now);
+        assert!((299..=300).contains(&delay.as_secs()));
+
+        // Out of quota: wait for the reset
+        let delay = scheduler.next_delay(&state(0, 600, PollHints::default()), now);
+        assert!(delay.as_secs() >= 600);
+
+        // X-Poll-Interval raises the floor
+        let hints = PollHints {
+            poll_interval_secs: Some(90),
+            ..Default::default()
+        };
+        let mut scheduler = RefreshScheduler::new(base);
+        assert_eq!(scheduler.next_delay(&state(60, 60, hints), now).as_secs(), 90);
+
+        // Throttling backs off exponentially and honours Retry-After
+        let throttled = PollHints {
+            throttled: true,
+            retry_after_secs: Some(30),
+            ..Default::default()
+        };
+        assert_eq!(scheduler.next_delay(&state(60, 60, throttled.clone()), now).as_secs(), 120);
+        assert_eq!(scheduler.next_delay(&state(60, 60, throttled.clone()), now).as_secs(), 240);
+        for _ in 0..10 {
+            scheduler.next_delay(&state(60, 60, throttled.clone()), now);
+        }
+        assert_eq!(scheduler.next_delay(&state(60, 60, throttled), now), MAX_BACKOFF);
+    }
+}
@@ -0,0 +1,177 @@
+use std::cmp::Ordering;
+
+use super::models::GithubRepo;
+
+/// Column the repositories table is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepoColumn {
+    Name,
+    #[default]
+    Stars,
+    Forks,
+    Issues,
+    Language,
+    Pushed,
+}
+
+impl RepoColumn {
+    /// Order cycled through from the keyboard
+    const ALL: [RepoColumn; 6] = [
+        RepoColumn::Name,
+        RepoColumn::Stars,
+        RepoColumn::Forks,
+        RepoColumn::Issues,
+        RepoColumn::Language,
+        RepoColumn::Pushed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Stars => "stars",
+            Self::Forks => "forks",
+            Self::Issues => "issues",
+            Self::Language => "language",
+            Self::Pushed => "pushed",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let i = Self::ALL.iter().position(|c| c == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Counts and dates read best largest/newest first, text A to Z
+    pub fn descending_by_default(&self) -> bool {
+        !matches!(self, Self::Name | Self::Language)
+    }
+
+    fn compare(&self, a: &GithubRepo, b: &GithubRepo) -> Ordering {
+        match self {
+            Self::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            Self::Stars => a.stargazers_count.cmp(&b.stargazers_count),
+            Self::Forks => a.forks_count.cmp(&b.forks_count),
+            Self::Issues => a.open_issues_count.cmp(&b.open_issues_count),
+            Self::Language => a.language.cmp(&b.language),
+            Self::Pushed => a.pushed_at.cmp(&b.pushed_at),
+        }
+    }
+}
+
+/// How the Repositories page's table is sorted and filtered
+#[derive(Debug, Clone)]
+pub struct RepoQuery {
+    pub sort: RepoColumn,
+    pub descending: bool,
+    /// Case-insensitive text matched against name, description and language
+    pub filter: String,
+    pub hide_forks: bool,
+    pub hide_archived: bool,
+}
+
+impl Default for RepoQuery {
+    fn default() -> Self {
+        Self {
+            sort: RepoColumn::default(),
+            descending: true,
+            filter: String::new(),
+            hide_forks: false,
+            hide_archived: false,
+        }
+    }
+}
+
+impl RepoQuery {
+    /// Sort by the next column, in that column's natural direction
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.descending = self.sort.descending_by_default();
+    }
+
+    fn matches(&self, repo: &GithubRepo) -> bool {
+        if (self.hide_forks && repo.fork) || (self.hide_archived && repo.archived) {
+            return false;
+        }
+        if self.filter.is_empty() {
+            return true;
+        }
+        let needle = self.filter.to_lowercase();
+        [
+            Some(repo.full_name.as_str()),
+            repo.description.as_deref(),
+            repo.language.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(&needle))
+    }
+
+    /// Repos passing the filter and toggles, sorted (ties broken by name)
+    pub fn apply<'a>(&self, repos: &'a [GithubRepo]) -> Vec<&'a GithubRepo> {
+        let mut shown: Vec<_> = repos.iter().filter(|r| self.matches(r)).collect();
+        shown.sort_by(|a, b| {
+            let order = self.sort.compare(a, b);
+            let order = if self.descending {
+                order.reverse()
+            } else {
+                order
+            };
+            order.then_with(|| a.name.cmp(&b.name))
+        });
+        shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(name: &str, stars: u32, language: Option<&str>, fork: bool) -> GithubRepo {
+        GithubRepo {
+            name: name.to_string(),
+            full_name: format!("me/{}", name),
+            description: None,
+            html_url: String::new(),
+            stargazers_count: stars,
+            forks_count: 0,
+            watchers_count: stars,
+            language: language.map(|l| l.to_string()),
+            updated_at: None,
+            pushed_at: None,
+            open_issues_count: 0,
+            fork,
+            default_branch: None,
+            archived: name == "old",
+        }
+    }
+
+    #[test]
+    fn test_sort_and_filter() {
+        let repos = vec![
+            repo("beta", 5, Some("Rust"), false),
+            repo("alpha", 20, Some("Go"), false),
+            repo("fork", 50, Some("Rust"), true),
+            repo("old", 1, None, false),
+        ];
+        let names = |shown: Vec<&GithubRepo>| -> Vec<String> {
+            shown.into_iter().map(|r| r.name.clone()).collect()
+        };
+
+        let mut query = RepoQuery::default();
+        assert_eq!(names(query.apply(&repos)), ["fork", "alpha", "beta", "old"]);
+
+        query.hide_forks = true;
+        query.hide_archived = true;
+        query.cycle_sort(); // forks; all tied, so by name
+        assert_eq!(query.sort, RepoColumn::Forks);
+        assert_eq!(names(query.apply(&repos)), ["alpha", "beta"]);
+
+        query.filter = "RUST".to_string();
+        assert_eq!(names(query.apply(&repos)), ["beta"]);
+
+        query.filter.clear();
+        query.sort = RepoColumn::Name;
+        query.descending = true;
+        assert_eq!(names(query.apply(&repos)), ["beta", "alpha"]);
+    }
+}
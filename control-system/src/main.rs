@@ -8,6 +8,7 @@ mod config;
 mod github;
 mod news;
 mod spotify;
+mod store;
 mod system;
 mod ui;
 mod util;
@@ -36,14 +37,14 @@ use ratatui::{
 };
 use tachyonfx::Effect;
 use tokio::sync::mpsc;
-use tracing::{error, info, Level};
+use tracing::{error, info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 use app::{Action, AppState, LogBuffer, LogWriterFactory, Page};
 use config::load::AppSettings;
 use config::Config;
 use github::GithubPoller;
-use system::SystemStats;
+use system::{ConnectivityMonitor, SystemStats};
 use ui::render_app;
 
 /// Target frame rate for the UI
@@ -85,10 +86,9 @@ async fn main() -> Result<()> {
             // Set for this process so pollers etc can find it
             std::env::set_var("GITHUB_USER", &username);
             // Save persistently so user doesn't need to re-enter
-            let _ = AppSettings {
-                github_user: username.clone(),
-            }
-            .save();
+            let mut settings = AppSettings::load().unwrap_or_default();
+            settings.github_user = username.clone();
+            let _ = settings.save();
             Config::build_with_user(username)?
         }
     };
@@ -918,8 +918,12 @@ fn render_boot_screen(
 
 /// Main application loop
 async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
-    // Query terminal image protocol capabilities before entering alternate screen
-    let mut picker = ratatui_image::picker::Picker::from_query_stdio().ok();
+    // Query terminal image protocol capabilities before entering alternate screen.
+    // Halfblocks is ratatui-image's truecolor-only fallback; our own renderer
+    // handles that case and also supports 256-colour and ASCII terminals.
+    let mut picker = ratatui_image::picker::Picker::from_query_stdio()
+        .ok()
+        .filter(|p| p.protocol_type() != ratatui_image::picker::ProtocolType::Halfblocks);
 
     // Set up terminal
     let mut terminal = setup_terminal()?;
@@ -929,23 +933,51 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
 
     // Initialize app state
     let mut state = AppState::new(config.reduced_motion, log_buffer);
+    state.has_github_token = config.has_token();
+    state.feed = config.feed.clone();
+
+    // Without an image protocol (Linux console, many SSH sessions), draw album
+    // art with the built-in cell renderer instead
+    if picker.is_none() {
+        let mode = ui::widgets::album_art::ArtMode::detect();
+        info!(
+            "No terminal image protocol detected, album art mode: {:?}",
+            mode
+        );
+        state.ui.art_fallback = Some(mode);
+    }
 
     // Set up GitHub poller
     let poller = GithubPoller::new(&config)?;
     let initial_github_state = poller.load_cached_state().await;
     state.github = initial_github_state.clone();
 
-    let (github_rx, github_cmd_tx) = poller.start(initial_github_state);
+    // Network pollers pause while the connectivity monitor reports offline
+    let mut connectivity_rx = ConnectivityMonitor::start(config.connectivity_probe.clone());
+
+    let (github_rx, github_cmd_tx) = poller.start(initial_github_state, connectivity_rx.clone());
 
     // Set up system stats poller
     let system_rx = SystemStats::start_poller(Duration::from_secs(2));
 
     // Set up news feed poller (refresh every 5 minutes)
-    let news_rx = news::NewsPoller::start(Duration::from_secs(300));
+    // The news and Spotify pollers start from what the store kept last run
+    let store = store::Store::new(&config.store_dir);
+    let news_rx = news::NewsPoller::start(
+        Duration::from_secs(300),
+        store.clone(),
+        connectivity_rx.clone(),
+    );
 
-    // Set up Spotify poller (poll every second for smooth progress bar)
+    // Set up Spotify poller (progress is interpolated between polls, so a
+    // few seconds is enough while playing; it slows down further when idle)
     let (spotify_rx, spotify_cmd_tx) = if spotify::SpotifyAuth::is_configured() {
-        let (rx, tx) = spotify::SpotifyPoller::start(Duration::from_secs(1));
+        let (rx, tx) = spotify::SpotifyPoller::start(
+            Duration::from_secs(5),
+            config.spotify.clone(),
+            store,
+            connectivity_rx.clone(),
+        );
         (Some(rx), Some(tx))
     } else {
         info!("Spotify not configured, skipping poller");
@@ -977,7 +1009,11 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
             match event::read()? {
                 Event::Key(key) => {
                     if key.kind == KeyEventKind::Press {
-                        let action = Action::from_key_event(key);
+                        let action = if state.ui.editing_repo_filter {
+                            Action::from_filter_key(key)
+                        } else {
+                            Action::from_key_event(key)
+                        };
                         let _ = action_tx.try_send(action);
                     }
                 }
@@ -985,19 +1021,7 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
                     if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
                         if mouse.row == 1 {
                             let col = mouse.column;
-                            let clicked_tab = if col >= 1 && col <= 14 {
-                                Some(0)
-                            } else if col >= 15 && col <= 24 {
-                                Some(1)
-                            } else if col >= 25 && col <= 37 {
-                                Some(2)
-                            } else if col >= 38 && col <= 49 {
-                                Some(3)
-                            } else if col >= 50 {
-                                Some(4)
-                            } else {
-                                None
-                            };
+                            let clicked_tab = ui::layout::tab_at_column(col);
                             if let Some(tab) = clicked_tab {
                                 let _ = action_tx.try_send(Action::GoToPage(tab));
                             }
@@ -1039,6 +1063,30 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
                     info!("Manual refresh requested");
                     let _ = github_cmd_tx.try_send(github::GithubCommand::Refresh);
                 }
+                Action::MarkNotificationRead => {
+                    if state.ui.current_page == Page::Dashboard {
+                        let index = state.selected_notification_index();
+                        if let Some(thread) = state.github.notification_threads().get(index) {
+                            let _ = github_cmd_tx.try_send(
+                                github::GithubCommand::MarkNotificationRead(thread.id.clone()),
+                            );
+                        }
+                    }
+                }
+                Action::AcknowledgeReleases => {
+                    // The releases box is only on the dashboard
+                    if state.ui.current_page == Page::Dashboard
+                        && state.github.new_release_count() > 0
+                    {
+                        let _ = github_cmd_tx.try_send(github::GithubCommand::AcknowledgeReleases);
+                    }
+                }
+                Action::MarkAllNotificationsRead => {
+                    if state.ui.current_page == Page::Dashboard {
+                        let _ =
+                            github_cmd_tx.try_send(github::GithubCommand::MarkAllNotificationsRead);
+                    }
+                }
                 Action::NextPage => {
                     let old_page = state.ui.current_page;
                     state.ui.current_page = state.ui.current_page.next();
@@ -1073,15 +1121,81 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
                     state.fx.animations_paused = !state.fx.animations_paused;
                     info!("Animations paused: {}", state.fx.animations_paused);
                 }
-                Action::ScrollUp => {
-                    state.ui.scroll_offset = state.ui.scroll_offset.saturating_sub(1);
-                }
-                Action::ScrollDown => {
-                    state.ui.scroll_offset = state.ui.scroll_offset.saturating_add(1);
+                Action::ScrollUp => match state.ui.current_page {
+                    Page::Dashboard => state.move_notification_selection(-1),
+                    Page::Repositories if state.ui.repo_detail.is_none() => {
+                        state.move_repo_selection(-1)
+                    }
+                    _ => state.ui.scroll_offset = state.ui.scroll_offset.saturating_sub(1),
+                },
+                Action::ScrollDown => match state.ui.current_page {
+                    Page::Dashboard => state.move_notification_selection(1),
+                    Page::Repositories if state.ui.repo_detail.is_none() => {
+                        state.move_repo_selection(1)
+                    }
+                    _ => state.ui.scroll_offset = state.ui.scroll_offset.saturating_add(1),
+                },
+                Action::SelectNext if state.ui.current_page == Page::Repositories => {
+                    // Open the highlighted repo, fetching it unless recently loaded
+                    let index = state.selected_repo_index();
+                    if let Some(repo) = state.listed_repos().get(index) {
+                        let full_name = repo.full_name.clone();
+                        let stale = state
+                            .github
+                            .repo_details
+                            .get(&full_name)
+                            .is_none_or(|d| d.is_stale(chrono::Utc::now()));
+                        if stale {
+                            let _ = github_cmd_tx.try_send(github::GithubCommand::FetchRepoDetail(
+                                full_name.clone(),
+                            ));
+                        }
+                        state.ui.repo_detail = Some(full_name);
+                    }
                 }
                 Action::SelectNext => {
                     state.ui.selected_index = state.ui.selected_index.saturating_add(1);
                 }
+                Action::Back => {
+                    state.ui.repo_detail = None;
+                }
+                Action::EditRepoFilter => {
+                    if state.ui.current_page == Page::Repositories && state.ui.repo_detail.is_none()
+                    {
+                        state.ui.editing_repo_filter = true;
+                    }
+                }
+                Action::FilterInput(c) => {
+                    state.ui.repo_query.filter.push(c);
+                    state.ui.repo_index = 0;
+                }
+                Action::FilterBackspace => {
+                    state.ui.repo_query.filter.pop();
+                    state.ui.repo_index = 0;
+                }
+                Action::FilterDone => {
+                    state.ui.editing_repo_filter = false;
+                }
+                Action::FilterClear => {
+                    state.ui.editing_repo_filter = false;
+                    state.ui.repo_query.filter.clear();
+                    state.ui.repo_index = 0;
+                }
+                Action::CycleRepoSort
+                | Action::ReverseRepoSort
+                | Action::ToggleForks
+                | Action::ToggleArchived => {
+                    if state.ui.current_page == Page::Repositories {
+                        let query = &mut state.ui.repo_query;
+                        match action {
+                            Action::CycleRepoSort => query.cycle_sort(),
+                            Action::ReverseRepoSort => query.descending = !query.descending,
+                            Action::ToggleForks => query.hide_forks = !query.hide_forks,
+                            _ => query.hide_archived = !query.hide_archived,
+                        }
+                        state.ui.repo_index = 0;
+                    }
+                }
                 Action::SelectPrev => {
                     state.ui.selected_index = state.ui.selected_index.saturating_sub(1);
                 }
@@ -1100,6 +1214,77 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
                         let _ = tx.try_send(spotify::SpotifyCommand::PrevTrack);
                     }
                 }
+                Action::SpotifySeekBack => {
+                    if let Some(ref tx) = spotify_cmd_tx {
+                        let _ = tx.try_send(spotify::SpotifyCommand::SeekRelative(
+                            spotify::poller::SKIP_BACK_MS,
+                        ));
+                    }
+                }
+                Action::SpotifySeekForward => {
+                    if let Some(ref tx) = spotify_cmd_tx {
+                        let _ = tx.try_send(spotify::SpotifyCommand::SeekRelative(
+                            spotify::poller::SKIP_FORWARD_MS,
+                        ));
+                    }
+                }
+                Action::CycleAccount => state.cycle_account(),
+                Action::CycleFeedFilter => {
+                    if state.ui.current_page == Page::Activity {
+                        state.ui.feed_kind =
+                            github::EventKind::cycle(state.ui.feed_kind, &state.feed.types);
+                        state.ui.scroll_offset = 0;
+                    }
+                }
+                Action::MuteRepo | Action::MuteActor => {
+                    if state.ui.current_page == Page::Activity {
+                        let target = state.selected_received_event().and_then(|event| {
+                            if action == Action::MuteRepo {
+                                Some(event.repo_name.clone())
+                            } else {
+                                event.actor.clone()
+                            }
+                        });
+                        if let Some(target) = target {
+                            let mute_repo = action == Action::MuteRepo;
+                            let mute = move |feed: &mut github::FeedSettings, target: &str| {
+                                let muted = if mute_repo {
+                                    &mut feed.muted_repos
+                                } else {
+                                    &mut feed.muted_actors
+                                };
+                                muted.insert(target.to_string());
+                            };
+                            mute(&mut state.feed, &target);
+                            state.push_toast(format!("Muted {} in the followed feed", target));
+                            // Persist only the new mute (the rest of the feed
+                            // settings may have come from the environment),
+                            // off the UI loop
+                            tokio::task::spawn_blocking(move || {
+                                match AppSettings::update(|s| mute(&mut s.received_feed, &target)) {
+                                    Ok(true) => {}
+                                    Ok(false) => {
+                                        warn!("Settings couldn't be loaded, not saving the mute")
+                                    }
+                                    Err(e) => warn!("Failed to save feed mutes: {}", e),
+                                }
+                            });
+                        }
+                    }
+                }
+                Action::SpotifySleepTimer => {
+                    if let Some(ref tx) = spotify_cmd_tx {
+                        let timer = spotify::SleepTimer::next_preset(
+                            state.spotify.sleep_timer.as_ref(),
+                            config.spotify.sleep_mode,
+                            &state.spotify.player,
+                            chrono::Utc::now(),
+                        );
+                        // Update locally so repeated presses keep cycling
+                        state.spotify.sleep_timer = timer.clone();
+                        let _ = tx.try_send(spotify::SpotifyCommand::SetSleepTimer(timer));
+                    }
+                }
                 Action::SpotifyReset => {
                     if state.ui.current_page == Page::Settings {
                         info!("Spotify reset requested from settings");
@@ -1128,9 +1313,67 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
                 }
             }
 
+            // Toast new stargazers and followers
+            let seen: std::collections::HashSet<_> =
+                state.github.announcements.iter().map(|e| &e.id).collect();
+            let fresh: Vec<String> = new_github
+                .announcements
+                .iter()
+                .filter(|e| !seen.contains(&e.id))
+                .map(|e| e.summary())
+                .collect();
+            for message in fresh.into_iter().rev() {
+                state.push_toast(message);
+            }
+
+            // Toast releases of watched repos that weren't known before
+            let fresh: Vec<String> = new_github
+                .watched_releases
+                .iter()
+                .filter(|r| !r.acknowledged)
+                .filter(|r| {
+                    !state
+                        .github
+                        .watched_releases
+                        .iter()
+                        .any(|old| old.repo == r.repo && old.release.tag == r.release.tag)
+                })
+                .map(|r| format!("{} released {}", r.repo, r.release.tag))
+                .collect();
+            for message in fresh {
+                state.push_toast(message);
+            }
+
+            // Flash the screen when a default-branch build newly breaks
+            if config.actions_alert && state.fx.should_animate() {
+                let already_broken: std::collections::HashSet<_> = state
+                    .github
+                    .workflow_runs
+                    .iter()
+                    .filter(|r| r.is_broken_default())
+                    .map(|r| r.id)
+                    .collect();
+                let newly_broken = new_github
+                    .workflow_runs
+                    .iter()
+                    .any(|r| r.is_broken_default() && !already_broken.contains(&r.id));
+                if newly_broken {
+                    effects.push(ui::fx::pulses::alert_pulse());
+                }
+            }
+
             state.github = new_github;
         }
 
+        if connectivity_rx.has_changed().unwrap_or(false) {
+            state.connectivity = connectivity_rx.borrow_and_update().clone();
+            state.push_toast(if state.connectivity.online {
+                "Back online, refreshing"
+            } else {
+                "Offline, showing the last known data"
+            });
+        }
+
         if system_rx.has_changed().unwrap_or(false) {
             state.system = system_rx.borrow().clone();
         }
@@ -1164,6 +1407,7 @@ async fn run_app(config: Config, log_buffer: LogBuffer) -> Result<()> {
 
         // Update animation state
         state.fx.tick(delta_ms);
+        state.expire_toasts();
         last_frame = Instant::now();
 
         // Render
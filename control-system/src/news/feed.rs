@@ -1,11 +1,15 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tokio::sync::watch;
 use tracing::{debug, error, info, warn};
 
+use crate::store::{Section, Store};
+use crate::system::connectivity::{back_online, Connectivity};
+
 /// A single news item/headline
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewsItem {
     pub title: String,
     pub link: String,
@@ -13,12 +17,14 @@ pub struct NewsItem {
     pub source: String,
 }
 
-/// Collection of news items
-#[derive(Debug, Clone, Default)]
+/// Collection of news items (the headlines are persisted in the store)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NewsFeed {
     pub items: Vec<NewsItem>,
     pub last_updated: Option<DateTime<Utc>>,
+    #[serde(skip)]
     pub is_loading: bool,
+    #[serde(skip)]
     pub error: Option<String>,
 }
 
@@ -26,8 +32,14 @@ pub struct NewsFeed {
 pub struct NewsPoller;
 
 impl NewsPoller {
-    /// Start background polling for news
-    pub fn start(poll_interval: Duration) -> watch::Receiver<NewsFeed> {
+    /// Start background polling for news, starting from the headlines
+    /// stored by the last run. Polls are skipped while `connectivity` is
+    /// offline, and the feed is fetched as soon as it's back.
+    pub fn start(
+        poll_interval: Duration,
+        store: Store,
+        mut connectivity: watch::Receiver<Connectivity>,
+    ) -> watch::Receiver<NewsFeed> {
         let (tx, rx) = watch::channel(NewsFeed {
             is_loading: true,
             ..Default::default()
@@ -40,16 +52,39 @@ impl NewsPoller {
                 .build()
                 .unwrap_or_default();
 
+            let mut last = match store.load_json::<NewsFeed>(Section::News).await {
+                Ok(Some(feed)) => {
+                    info!("Restored {} stored headlines", feed.items.len());
+                    feed
+                }
+                Ok(None) => NewsFeed::default(),
+                Err(e) => {
+                    warn!("Failed to load stored headlines: {}", e);
+                    NewsFeed::default()
+                }
+            };
+
             let mut interval = tokio::time::interval(poll_interval);
 
             loop {
-                interval.tick().await;
-                
-                let mut feed = NewsFeed {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if !connectivity.borrow().online {
+                            debug!("Offline, skipping news refresh");
+                            continue;
+                        }
+                    }
+                    _ = back_online(&mut connectivity) => {
+                        info!("Back online, refreshing news");
+                        interval.reset();
+                    }
+                }
+
+                // Keep showing the last headlines while fetching
+                let _ = tx.send(NewsFeed {
                     is_loading: true,
-                    ..Default::default()
-                };
-                let _ = tx.send(feed.clone());
+                    ..last.clone()
+                });
 
                 // Fetch from multiple Malaysian news sources
                 let mut all_items = Vec::new();
@@ -87,18 +122,26 @@ impl NewsPoller {
                 });
                 all_items.truncate(10);
 
-                feed = NewsFeed {
-                    items: all_items,
-                    last_updated: Some(Utc::now()),
-                    is_loading: false,
-                    error: None,
-                };
-
-                if feed.items.is_empty() {
-                    feed.error = Some("No news available".to_string());
+                let feed = if all_items.is_empty() {
+                    // Every source failed: fall back to the last headlines
+                    NewsFeed {
+                        is_loading: false,
+                        error: Some("No news available".to_string()),
+                        ..last.clone()
+                    }
                 } else {
-                    info!("News feed updated: {} headlines", feed.items.len());
-                }
+                    info!("News feed updated: {} headlines", all_items.len());
+                    last = NewsFeed {
+                        items: all_items,
+                        last_updated: Some(Utc::now()),
+                        is_loading: false,
+                        error: None,
+                    };
+                    if let Err(e) = store.save_json(Section::News, &last).await {
+                        error!("Failed to store headlines: {}", e);
+                    }
+                    last.clone()
+                };
 
                 if tx.send(feed).is_err() {
                     break;
@@ -1,16 +1,17 @@
 use anyhow::{anyhow, Result};
 use base64::Engine;
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 use super::state::SpotifyTokens;
+use crate::store::{read_with_fallback, write_atomic};
 
 /// Redirect URI - uses explicit IPv4 loopback per Spotify requirements.
 /// `localhost` is not allowed; must use `127.0.0.1`. HTTP is permitted for loopback.
 /// Nothing actually listens here; the user copies the URL from their browser.
 const REDIRECT_URI: &str = "http://127.0.0.1:8585/callback";
-const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+pub(super) const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
 const AUTH_URL: &str = "https://accounts.spotify.com/authorize";
 const SCOPES: &str =
     "user-read-playback-state user-modify-playback-state user-read-currently-playing";
@@ -32,17 +33,24 @@ impl SpotifyAuth {
 
     /// Load tokens from disk
     pub fn load_tokens() -> Option<SpotifyTokens> {
-        let path = Self::token_path();
-        let data = std::fs::read_to_string(&path).ok()?;
-        serde_json::from_str(&data).ok()
+        read_with_fallback(&Self::token_path(), |data| {
+            Ok(serde_json::from_slice(data)?)
+        })
+        .unwrap_or_else(|e| {
+            warn!("Ignoring Spotify tokens: {:#}", e);
+            None
+        })
     }
 
     /// Save tokens to disk
     pub fn save_tokens(tokens: &SpotifyTokens) -> Result<()> {
-        let path = Self::token_path();
+        Self::save_tokens_to(&Self::token_path(), tokens)
+    }
+
+    /// Save tokens to a specific file
+    pub fn save_tokens_to(path: &Path, tokens: &SpotifyTokens) -> Result<()> {
         let data = serde_json::to_string_pretty(tokens)?;
-        std::fs::write(&path, data)?;
-        Ok(())
+        write_atomic(path, data.as_bytes())
     }
 
     /// Check if Spotify is configured (tokens exist)
@@ -160,8 +168,9 @@ impl SpotifyAuth {
         Ok(tokens)
     }
 
-    /// Refresh an expired access token
-    pub async fn refresh_token(tokens: &SpotifyTokens) -> Result<SpotifyTokens> {
+    /// Exchange the refresh token for a new access token at `token_url`.
+    /// Does not write anything to disk.
+    pub async fn request_refresh(token_url: &str, tokens: &SpotifyTokens) -> Result<SpotifyTokens> {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(10))
             .connect_timeout(std::time::Duration::from_secs(5))
@@ -174,7 +183,7 @@ impl SpotifyAuth {
             ("client_id", &tokens.client_id),
         ];
 
-        let resp = client.post(TOKEN_URL).form(&params).send().await?;
+        let resp = client.post(token_url).form(&params).send().await?;
 
         if !resp.status().is_success() {
             let text = resp.text().await.unwrap_or_default();
@@ -193,14 +202,11 @@ impl SpotifyAuth {
             .unwrap_or_else(|| tokens.refresh_token.clone());
         let expires_in = body["expires_in"].as_u64().unwrap_or(3600);
 
-        let new_tokens = SpotifyTokens {
+        Ok(SpotifyTokens {
             access_token,
             refresh_token,
             expires_at: chrono::Utc::now() + chrono::Duration::seconds(expires_in as i64),
             client_id: tokens.client_id.clone(),
-        };
-
-        Self::save_tokens(&new_tokens)?;
-        Ok(new_tokens)
+        })
     }
 }
@@ -1,32 +1,90 @@
 use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use std::time::Duration;
 use tracing::{debug, warn};
 
-use super::auth::SpotifyAuth;
-use super::state::SpotifyTokens;
+use super::auth::{SpotifyAuth, TOKEN_URL};
+use super::state::{EpisodeInfo, MediaKind, SpotifyTokens};
 
 const API_BASE: &str = "https://api.spotify.com/v1";
 
+/// Wait used when a 429 response carries no usable `Retry-After` header
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(5);
+
+/// Spotify answered 429 Too Many Requests
+#[derive(Debug, thiserror::Error)]
+#[error("Spotify rate limited, retry after {}s", retry_after.as_secs())]
+pub struct RateLimited {
+    pub retry_after: Duration,
+}
+
+/// Turn a 429 response into a [`RateLimited`] error carrying `Retry-After`
+fn check_rate_limit(resp: &reqwest::Response) -> Result<()> {
+    if resp.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Ok(());
+    }
+    let retry_after = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER);
+    warn!(
+        "Spotify rate limited, retrying in {}s",
+        retry_after.as_secs()
+    );
+    Err(RateLimited { retry_after }.into())
+}
+
 /// Spotify Web API client
 pub struct SpotifyClient {
     client: reqwest::Client,
     tokens: SpotifyTokens,
+    api_base: String,
+    token_url: String,
+    token_path: PathBuf,
 }
 
 impl SpotifyClient {
     pub fn new(tokens: SpotifyTokens) -> Self {
+        Self::with_endpoints(tokens, API_BASE, TOKEN_URL)
+    }
+
+    /// Create a client talking to a different Web API base and token URL
+    /// (e.g. a local mock server)
+    pub fn with_endpoints(
+        tokens: SpotifyTokens,
+        api_base: impl Into<String>,
+        token_url: impl Into<String>,
+    ) -> Self {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(10))
             .connect_timeout(std::time::Duration::from_secs(5))
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
-        Self { client, tokens }
+        Self {
+            client,
+            tokens,
+            api_base: api_base.into().trim_end_matches('/').to_string(),
+            token_url: token_url.into(),
+            token_path: SpotifyAuth::token_path(),
+        }
+    }
+
+    /// Persist refreshed tokens to `path` instead of the default location
+    #[cfg(test)]
+    pub fn with_token_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.token_path = path.into();
+        self
     }
 
     /// Ensure we have a valid access token, refreshing if needed
     async fn ensure_token(&mut self) -> Result<()> {
         if self.tokens.is_expired() {
             debug!("Spotify token expired, refreshing...");
-            self.tokens = SpotifyAuth::refresh_token(&self.tokens).await?;
+            self.tokens = SpotifyAuth::request_refresh(&self.token_url, &self.tokens).await?;
+            SpotifyAuth::save_tokens_to(&self.token_path, &self.tokens)?;
         }
         Ok(())
     }
@@ -37,13 +95,14 @@ impl SpotifyClient {
 
         let resp = self
             .client
-            .get(&format!(
+            .get(format!(
                 "{}/me/player?additional_types=track,episode",
-                API_BASE
+                self.api_base
             ))
             .bearer_auth(&self.tokens.access_token)
             .send()
             .await?;
+        check_rate_limit(&resp)?;
 
         if resp.status().as_u16() == 204 {
             // No active playback
@@ -66,9 +125,9 @@ impl SpotifyClient {
         self.ensure_token().await?;
 
         let endpoint = if currently_playing {
-            format!("{}/me/player/pause", API_BASE)
+            format!("{}/me/player/pause", self.api_base)
         } else {
-            format!("{}/me/player/play", API_BASE)
+            format!("{}/me/player/play", self.api_base)
         };
 
         let resp = self
@@ -79,6 +138,7 @@ impl SpotifyClient {
             .body(reqwest::Body::from(vec![]))
             .send()
             .await?;
+        check_rate_limit(&resp)?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -95,12 +155,13 @@ impl SpotifyClient {
 
         let resp = self
             .client
-            .post(&format!("{}/me/player/next", API_BASE))
+            .post(format!("{}/me/player/next", self.api_base))
             .header("Content-Length", "0")
             .bearer_auth(&self.tokens.access_token)
             .body(reqwest::Body::from(vec![]))
             .send()
             .await?;
+        check_rate_limit(&resp)?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -117,12 +178,13 @@ impl SpotifyClient {
 
         let resp = self
             .client
-            .post(&format!("{}/me/player/previous", API_BASE))
+            .post(format!("{}/me/player/previous", self.api_base))
             .header("Content-Length", "0")
             .bearer_auth(&self.tokens.access_token)
             .body(reqwest::Body::from(vec![]))
             .send()
             .await?;
+        check_rate_limit(&resp)?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -133,16 +195,70 @@ impl SpotifyClient {
         Ok(())
     }
 
+    /// Set the volume of the active device (0-100)
+    pub async fn set_volume(&mut self, percent: u32) -> Result<()> {
+        self.ensure_token().await?;
+
+        let resp = self
+            .client
+            .put(format!(
+                "{}/me/player/volume?volume_percent={}",
+                self.api_base,
+                percent.min(100)
+            ))
+            .header("Content-Length", "0")
+            .bearer_auth(&self.tokens.access_token)
+            .body(reqwest::Body::from(vec![]))
+            .send()
+            .await?;
+        check_rate_limit(&resp)?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            warn!("Spotify volume failed ({}): {}", status, text);
+            return Err(anyhow!("Spotify volume failed: {} {}", status, text));
+        }
+        Ok(())
+    }
+
+    /// Seek to a position in the current item
+    pub async fn seek(&mut self, position_ms: u64) -> Result<()> {
+        self.ensure_token().await?;
+
+        let resp = self
+            .client
+            .put(format!(
+                "{}/me/player/seek?position_ms={}",
+                self.api_base, position_ms
+            ))
+            .header("Content-Length", "0")
+            .bearer_auth(&self.tokens.access_token)
+            .body(reqwest::Body::from(vec![]))
+            .send()
+            .await?;
+        check_rate_limit(&resp)?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            warn!("Spotify seek failed ({}): {}", status, text);
+            return Err(anyhow!("Spotify seek failed: {} {}", status, text));
+        }
+        Ok(())
+    }
+
     /// Get the user's queue (up next)
     pub async fn get_queue(&mut self) -> Result<Vec<QueueItem>> {
         self.ensure_token().await?;
 
         let resp = self
             .client
-            .get(&format!("{}/me/player/queue", API_BASE))
+            .get(format!("{}/me/player/queue", self.api_base))
             .bearer_auth(&self.tokens.access_token)
             .send()
             .await?;
+        check_rate_limit(&resp)?;
 
         if !resp.status().is_success() {
             return Ok(Vec::new());
@@ -195,11 +311,17 @@ pub struct PlaybackResponse {
     pub progress_ms: u64,
     pub duration_ms: u64,
     pub album_art_url: Option<String>,
+    /// Volume of the active device, if it reports one
+    pub volume_percent: Option<u32>,
+    pub kind: MediaKind,
+    /// Show, publisher and resume point for podcast episodes
+    pub episode: Option<EpisodeInfo>,
 }
 
 fn parse_playback(body: &serde_json::Value) -> PlaybackResponse {
     let is_playing = body["is_playing"].as_bool().unwrap_or(false);
     let progress_ms = body["progress_ms"].as_u64().unwrap_or(0);
+    let volume_percent = body["device"]["volume_percent"].as_u64().map(|v| v as u32);
 
     // Handle null item (happens between tracks, during ads, etc.)
     let item = &body["item"];
@@ -216,12 +338,57 @@ fn parse_playback(body: &serde_json::Value) -> PlaybackResponse {
             progress_ms,
             duration_ms: 0,
             album_art_url: None,
+            volume_percent,
+            kind: MediaKind::parse(body["currently_playing_type"].as_str().unwrap_or("")),
+            episode: None,
         };
     }
 
+    let kind = MediaKind::parse(
+        item["type"]
+            .as_str()
+            .or_else(|| body["currently_playing_type"].as_str())
+            .unwrap_or(""),
+    );
     let track_name = item["name"].as_str().unwrap_or("").to_string();
+    let duration_ms = item["duration_ms"].as_u64().unwrap_or(0);
+
+    if kind == MediaKind::Episode {
+        let show = &item["show"];
+        let show_name = show["name"].as_str().unwrap_or("").to_string();
+        let publisher = show["publisher"].as_str().unwrap_or("").to_string();
+        let album_art_url = item["images"]
+            .as_array()
+            .or_else(|| show["images"].as_array())
+            .and_then(|imgs| imgs.first())
+            .and_then(|img| img["url"].as_str())
+            .map(|s| s.to_string());
+
+        return PlaybackResponse {
+            is_playing,
+            track_name,
+            artist_name: if publisher.is_empty() {
+                show_name.clone()
+            } else {
+                publisher.clone()
+            },
+            album_name: show_name.clone(),
+            progress_ms,
+            duration_ms,
+            album_art_url,
+            volume_percent,
+            kind,
+            episode: Some(EpisodeInfo {
+                show_name,
+                publisher,
+                resume_position_ms: item["resume_point"]["resume_position_ms"].as_u64(),
+                fully_played: item["resume_point"]["fully_played"]
+                    .as_bool()
+                    .unwrap_or(false),
+            }),
+        };
+    }
 
-    // Handle both tracks (artists array) and episodes (show.publisher)
     let artists: Vec<String> = item["artists"]
         .as_array()
         .map(|arr| {
@@ -230,26 +397,10 @@ fn parse_playback(body: &serde_json::Value) -> PlaybackResponse {
                 .collect()
         })
         .unwrap_or_default();
-    let artist_name = if artists.is_empty() {
-        // Might be a podcast episode - try show name
-        item["show"]["name"].as_str().unwrap_or("").to_string()
-    } else {
-        artists.join(", ")
-    };
-
-    // Handle both tracks (album) and episodes (show)
-    let album_name = item["album"]["name"]
-        .as_str()
-        .or_else(|| item["show"]["name"].as_str())
-        .unwrap_or("")
-        .to_string();
-
-    let duration_ms = item["duration_ms"].as_u64().unwrap_or(0);
-
-    // Try album images first, then show images
+    let artist_name = artists.join(", ");
+    let album_name = item["album"]["name"].as_str().unwrap_or("").to_string();
     let album_art_url = item["album"]["images"]
         .as_array()
-        .or_else(|| item["show"]["images"].as_array())
         .and_then(|imgs| imgs.first())
         .and_then(|img| img["url"].as_str())
         .map(|s| s.to_string());
@@ -262,5 +413,284 @@ fn parse_playback(body: &serde_json::Value) -> PlaybackResponse {
         progress_ms,
         duration_ms,
         album_art_url,
+        volume_percent,
+        kind,
+        episode: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn tokens(expired: bool) -> SpotifyTokens {
+        let offset = if expired { -60 } else { 3600 };
+        SpotifyTokens {
+            access_token: "old-token".to_string(),
+            refresh_token: "refresh-me".to_string(),
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(offset),
+            client_id: "client".to_string(),
+        }
+    }
+
+    fn mock_client(server: &MockServer, tokens: SpotifyTokens) -> SpotifyClient {
+        SpotifyClient::with_endpoints(tokens, server.uri(), format!("{}/api/token", server.uri()))
+            .with_token_path(std::env::temp_dir().join("control-system-test-unused.json"))
+    }
+
+    #[tokio::test]
+    async fn test_playback_track() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/me/player"))
+            .and(query_param("additional_types", "track,episode"))
+            .and(header("authorization", "Bearer old-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "is_playing": true,
+                "progress_ms": 42000,
+                "currently_playing_type": "track",
+                "device": {"name": "Desk", "volume_percent": 65},
+                "item": {
+                    "name": "Song",
+                    "duration_ms": 180000,
+                    "artists": [{"name": "A"}, {"name": "B"}],
+                    "album": {"name": "Album", "images": [{"url": "https://img/1"}]}
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(false));
+        let pb = client.get_playback().await.unwrap().unwrap();
+        assert!(pb.is_playing);
+        assert_eq!(pb.track_name, "Song");
+        assert_eq!(pb.artist_name, "A, B");
+        assert_eq!(pb.album_name, "Album");
+        assert_eq!(pb.progress_ms, 42000);
+        assert_eq!(pb.duration_ms, 180000);
+        assert_eq!(pb.album_art_url.as_deref(), Some("https://img/1"));
+        assert_eq!(pb.volume_percent, Some(65));
+        assert_eq!(pb.kind, MediaKind::Track);
+        assert!(pb.episode.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_playback_episode() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/me/player"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "is_playing": false,
+                "progress_ms": 1000,
+                "currently_playing_type": "episode",
+                "item": {
+                    "type": "episode",
+                    "name": "Episode 12",
+                    "duration_ms": 3600000,
+                    "resume_point": {"fully_played": false, "resume_position_ms": 754000},
+                    "show": {
+                        "name": "The Show",
+                        "publisher": "Publisher",
+                        "images": [{"url": "https://img/show"}]
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(false));
+        let pb = client.get_playback().await.unwrap().unwrap();
+        assert_eq!(pb.kind, MediaKind::Episode);
+        assert_eq!(pb.track_name, "Episode 12");
+        assert_eq!(pb.artist_name, "Publisher");
+        assert_eq!(pb.album_name, "The Show");
+        assert_eq!(pb.album_art_url.as_deref(), Some("https://img/show"));
+        assert_eq!(
+            pb.episode,
+            Some(EpisodeInfo {
+                show_name: "The Show".to_string(),
+                publisher: "Publisher".to_string(),
+                resume_position_ms: Some(754000),
+                fully_played: false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_playback_no_device() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/me/player"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(false));
+        assert!(client.get_playback().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_playback_null_item() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/me/player"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "is_playing": true,
+                "progress_ms": 0,
+                "currently_playing_type": "ad",
+                "item": null
+            })))
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(false));
+        let pb = client.get_playback().await.unwrap().unwrap();
+        assert!(pb.track_name.is_empty());
+        assert_eq!(pb.duration_ms, 0);
+        assert_eq!(pb.kind, MediaKind::Other);
+    }
+
+    #[tokio::test]
+    async fn test_queue() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/me/player/queue"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "currently_playing": null,
+                "queue": [
+                    {"name": "Next", "artists": [{"name": "X"}]},
+                    {"name": "Pod", "show": {"name": "Some Show"}}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(false));
+        let queue = client.get_queue().await.unwrap();
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[0].name, "Next");
+        assert_eq!(queue[0].artist, "X");
+        assert_eq!(queue[1].artist, "Some Show");
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_expired_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/token"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=refresh-me"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "new-token",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/player"))
+            .and(header("authorization", "Bearer new-token"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let token_path = dir.path().join("spotify.json");
+        let mut client = mock_client(&server, tokens(true)).with_token_path(&token_path);
+
+        // Second call reuses the refreshed token
+        assert!(client.get_playback().await.unwrap().is_none());
+        assert!(client.get_playback().await.unwrap().is_none());
+
+        let saved: SpotifyTokens =
+            serde_json::from_str(&std::fs::read_to_string(&token_path).unwrap()).unwrap();
+        assert_eq!(saved.access_token, "new-token");
+        // Spotify may omit the refresh token; the old one is kept
+        assert_eq!(saved.refresh_token, "refresh-me");
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/token"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_string(r#"{"error":"invalid_grant"}"#),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/player"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(true));
+        let err = client.get_playback().await.unwrap_err();
+        assert!(err.to_string().contains("Token refresh failed"));
+        assert!(err.to_string().contains("invalid_grant"));
+    }
+
+    #[tokio::test]
+    async fn test_commands() {
+        let server = MockServer::start().await;
+        for (verb, route) in [
+            ("PUT", "/me/player/pause"),
+            ("PUT", "/me/player/play"),
+            ("POST", "/me/player/next"),
+            ("POST", "/me/player/previous"),
+            ("PUT", "/me/player/seek"),
+        ] {
+            Mock::given(method(verb))
+                .and(path(route))
+                .and(header("authorization", "Bearer old-token"))
+                .respond_with(ResponseTemplate::new(204))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("PUT"))
+            .and(path("/me/player/volume"))
+            .and(query_param("volume_percent", "100"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(false));
+        client.toggle_playback(true).await.unwrap();
+        client.toggle_playback(false).await.unwrap();
+        client.next_track().await.unwrap();
+        client.prev_track().await.unwrap();
+        client.set_volume(140).await.unwrap();
+        client.seek(90_000).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_command_failure_and_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/me/player/next"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("NO_ACTIVE_DEVICE"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/player"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "7"))
+            .mount(&server)
+            .await;
+
+        let mut client = mock_client(&server, tokens(false));
+        let err = client.next_track().await.unwrap_err();
+        assert!(err.to_string().contains("NO_ACTIVE_DEVICE"));
+
+        let err = client.get_playback().await.unwrap_err();
+        let limited = err.downcast_ref::<RateLimited>().unwrap();
+        assert_eq!(limited.retry_after, Duration::from_secs(7));
     }
 }
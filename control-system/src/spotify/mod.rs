@@ -1,9 +1,11 @@
 pub mod auth;
 pub mod client;
 pub mod poller;
+pub mod schedule;
 pub mod state;
 
 pub use auth::SpotifyAuth;
 pub use client::SpotifyClient;
 pub use poller::{SpotifyCommand, SpotifyPoller};
+pub use schedule::{SleepTimer, SpotifySettings};
 pub use state::{PlayerState, SpotifyState};
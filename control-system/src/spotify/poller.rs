@@ -1,10 +1,32 @@
 use std::time::Duration;
 use tokio::sync::{mpsc, watch};
-use tracing::{error, info, warn};
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, error, info, warn};
 
 use super::auth::SpotifyAuth;
-use super::client::SpotifyClient;
-use super::state::{AlbumArt, PlayerState, SpotifyState};
+use super::client::{PlaybackResponse, RateLimited, SpotifyClient};
+use super::schedule::{
+    FadeOut, PlaybackSchedule, ScheduleAction, SleepTimer, SpotifySettings, StopMode,
+};
+use super::state::{AlbumArt, PlayerState, QueueItem, SpotifyState};
+use crate::store::{Section, Store};
+use crate::system::connectivity::{back_online, Connectivity};
+
+/// Poll interval when the user has no active Spotify device
+const IDLE_POLL: Duration = Duration::from_secs(15);
+/// Poll interval while playback is paused
+const PAUSED_POLL: Duration = Duration::from_secs(5);
+/// Upper bound for the error backoff
+const MAX_BACKOFF: Duration = Duration::from_secs(120);
+/// How often to refresh the queue when the track has not changed
+const QUEUE_REFRESH: Duration = Duration::from_secs(30);
+/// How often sleep timers, schedules and fades are checked
+const AUTOMATION_TICK: Duration = Duration::from_secs(1);
+/// Delay before re-polling after changing playback
+const COMMAND_SETTLE: Duration = Duration::from_millis(300);
+/// Skip back/forward amounts for the seek keys (podcast-style 15s / 30s)
+pub const SKIP_BACK_MS: i64 = -15_000;
+pub const SKIP_FORWARD_MS: i64 = 30_000;
 
 /// Commands that can be sent to the Spotify poller
 #[derive(Debug, Clone)]
@@ -12,6 +34,10 @@ pub enum SpotifyCommand {
     TogglePlayback,
     NextTrack,
     PrevTrack,
+    /// Seek relative to the current position (milliseconds, may be negative)
+    SeekRelative(i64),
+    /// Arm (or with `None`, cancel) the sleep timer
+    SetSleepTimer(Option<SleepTimer>),
     Stop,
 }
 
@@ -19,9 +45,17 @@ pub struct SpotifyPoller;
 
 impl SpotifyPoller {
     /// Start the Spotify polling loop.
+    /// `poll_interval` is the cadence while music is playing; the poller slows
+    /// down when paused or idle and backs off on errors. The sleep timer and
+    /// the schedules from `settings` are executed by the same loop. The last
+    /// known track is restored from `store` (paused) until the first poll.
+    /// Polling and automation pause while `connectivity` is offline.
     /// Returns a watch receiver for state updates and a command sender.
     pub fn start(
         poll_interval: Duration,
+        settings: SpotifySettings,
+        store: Store,
+        mut connectivity: watch::Receiver<Connectivity>,
     ) -> (watch::Receiver<SpotifyState>, mpsc::Sender<SpotifyCommand>) {
         let (state_tx, state_rx) = watch::channel(SpotifyState::default());
         let (cmd_tx, mut cmd_rx) = mpsc::channel::<SpotifyCommand>(16);
@@ -45,123 +79,176 @@ impl SpotifyPoller {
                 .connect_timeout(std::time::Duration::from_secs(5))
                 .build()
                 .unwrap_or_else(|_| reqwest::Client::new());
-            let mut interval = tokio::time::interval(poll_interval);
-            let mut cached_art_url: Option<String> = None;
-            let mut cached_art: Option<AlbumArt> = None;
+            let mut art = ArtCache::default();
+            let mut stored_track = None;
+            if let Some(player) = restore_now_playing(&store, &mut art).await {
+                stored_track = Some(track_key(&player));
+                state_tx.send_modify(|s| s.player = player);
+            }
+            let mut failures: u32 = 0;
+            let mut last_queue_fetch: Option<Instant> = None;
+            let mut automation = Automation::new(&settings);
+
+            let next_poll = tokio::time::sleep(Duration::ZERO);
+            tokio::pin!(next_poll);
+            let mut tick = tokio::time::interval(AUTOMATION_TICK);
+            tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
             info!("Spotify poller started");
 
             loop {
                 tokio::select! {
-                    _ = interval.tick() => {
-                        match client.get_playback().await {
+                    _ = &mut next_poll => {
+                        if !connectivity.borrow().online {
+                            next_poll.as_mut().reset(Instant::now() + IDLE_POLL);
+                            continue;
+                        }
+                        let delay = match client.get_playback().await {
                             Ok(Some(pb)) => {
-                                // Fetch album art if URL changed
-                                let art_url = pb.album_art_url.clone();
-                                if art_url != cached_art_url {
-                                    cached_art = match &art_url {
-                                        Some(url) => fetch_album_art(&http_client, url).await,
-                                        None => None,
-                                    };
-                                    cached_art_url = art_url;
+                                if art.update(&http_client, pb.album_art_url.as_deref()).await {
+                                    store_album_art(&store, &art).await;
                                 }
 
-                                // Fetch queue
-                                let queue = client.get_queue().await.unwrap_or_default()
-                                    .into_iter()
-                                    .map(|q| super::state::QueueItem { name: q.name, artist: q.artist })
-                                    .collect();
-
-                                let _ = state_tx.send(SpotifyState {
-                                    player: PlayerState {
-                                        is_playing: pb.is_playing,
-                                        track_name: pb.track_name,
-                                        artist_name: pb.artist_name,
-                                        album_name: pb.album_name,
-                                        progress_ms: pb.progress_ms,
-                                        duration_ms: pb.duration_ms,
-                                        album_art_url: pb.album_art_url,
-                                        album_art: cached_art.clone(),
-                                        last_updated: Some(chrono::Utc::now()),
-                                        queue,
-                                    },
-                                    connected: true,
-                                    error: None,
+                                // Only re-fetch the queue when the track changes,
+                                // or periodically in case the user edited it
+                                let prev = state_tx.borrow().player.clone();
+                                let track_changed = prev.track_name != pb.track_name
+                                    || prev.artist_name != pb.artist_name;
+                                let queue_stale = last_queue_fetch
+                                    .is_none_or(|at| at.elapsed() >= QUEUE_REFRESH);
+                                let mut queue_error = None;
+                                let queue = if track_changed || queue_stale {
+                                    match client.get_queue().await {
+                                        Ok(items) => {
+                                            last_queue_fetch = Some(Instant::now());
+                                            items
+                                                .into_iter()
+                                                .map(|q| QueueItem { name: q.name, artist: q.artist })
+                                                .collect()
+                                        }
+                                        // Keep the old queue and retry on the next poll,
+                                        // backing off like a failed playback poll
+                                        Err(e) => {
+                                            warn!("Spotify queue error: {}", e);
+                                            queue_error = Some(e);
+                                            prev.queue
+                                        }
+                                    }
+                                } else {
+                                    prev.queue
+                                };
+
+                                let player = player_state(pb, &art, queue);
+                                let delay = match &queue_error {
+                                    Some(e) => {
+                                        failures = failures.saturating_add(1);
+                                        error_delay(poll_interval, failures, e)
+                                    }
+                                    None => {
+                                        failures = 0;
+                                        poll_delay(poll_interval, Some(&player))
+                                    }
+                                };
+                                store_now_playing(&store, &player, &mut stored_track).await;
+                                state_tx.send_modify(|s| {
+                                    s.player = player;
+                                    s.connected = true;
+                                    s.error = None;
                                 });
+                                delay
                             }
                             Ok(None) => {
-                                let _ = state_tx.send(SpotifyState {
-                                    player: PlayerState::default(),
-                                    connected: true,
-                                    error: None,
+                                failures = 0;
+                                state_tx.send_modify(|s| {
+                                    s.player = PlayerState::default();
+                                    s.connected = true;
+                                    s.error = None;
                                 });
+                                poll_delay(poll_interval, None)
                             }
                             Err(e) => {
+                                failures = failures.saturating_add(1);
                                 warn!("Spotify poll error: {}", e);
-                                let _ = state_tx.send(SpotifyState {
-                                    connected: false,
-                                    error: Some(e.to_string()),
-                                    ..state_tx.borrow().clone()
+                                state_tx.send_modify(|s| {
+                                    s.connected = false;
+                                    s.error = Some(e.to_string());
                                 });
+                                error_delay(poll_interval, failures, &e)
                             }
+                        };
+                        debug!("Next Spotify poll in {:?}", delay);
+                        next_poll.as_mut().reset(Instant::now() + delay);
+                    }
+                    _ = back_online(&mut connectivity) => {
+                        info!("Back online, polling Spotify");
+                        next_poll.as_mut().reset(Instant::now());
+                    }
+                    _ = tick.tick() => {
+                        // Clone before send() (see the deadlock note below)
+                        let player = state_tx.borrow().player.clone();
+                        let online = connectivity.borrow().online;
+                        if online && automation.run(&mut client, &player).await {
+                            next_poll.as_mut().reset(Instant::now() + COMMAND_SETTLE);
                         }
+                        automation.publish(&state_tx);
                     }
                     Some(cmd) = cmd_rx.recv() => {
-                        match cmd {
+                        let result = match cmd {
                             SpotifyCommand::TogglePlayback => {
+                                automation.cancel_fade(&mut client).await;
                                 let is_playing = state_tx.borrow().player.is_playing;
-                                if let Err(e) = client.toggle_playback(is_playing).await {
-                                    error!("Spotify toggle error: {}", e);
-                                }
+                                client.toggle_playback(is_playing).await
                             }
-                            SpotifyCommand::NextTrack => {
-                                if let Err(e) = client.next_track().await {
-                                    error!("Spotify next error: {}", e);
-                                }
+                            SpotifyCommand::NextTrack => client.next_track().await,
+                            SpotifyCommand::PrevTrack => client.prev_track().await,
+                            SpotifyCommand::SeekRelative(offset_ms) => {
+                                let target = seek_target(&state_tx.borrow().player, offset_ms);
+                                client.seek(target).await
                             }
-                            SpotifyCommand::PrevTrack => {
-                                if let Err(e) = client.prev_track().await {
-                                    error!("Spotify prev error: {}", e);
+                            SpotifyCommand::SetSleepTimer(timer) => {
+                                match &timer {
+                                    Some(t) => info!("Sleep timer set: {}", t.label(chrono::Utc::now())),
+                                    None => info!("Sleep timer cancelled"),
                                 }
+                                automation.sleep = timer;
+                                automation.publish(&state_tx);
+                                continue;
                             }
                             SpotifyCommand::Stop => {
                                 info!("Spotify poller stopping");
                                 return;
                             }
+                        };
+                        if let Err(e) = result {
+                            error!("Spotify command {:?} failed: {}", cmd, e);
+                            if let Some(rl) = e.downcast_ref::<RateLimited>() {
+                                next_poll.as_mut().reset(Instant::now() + rl.retry_after);
+                                continue;
+                            }
                         }
+
                         // After a command, fetch updated state quickly
-                        tokio::time::sleep(Duration::from_millis(300)).await;
+                        tokio::time::sleep(COMMAND_SETTLE).await;
                         if let Ok(Some(pb)) = client.get_playback().await {
-                            // Fetch album art if URL changed
-                            let art_url = pb.album_art_url.clone();
-                            if art_url != cached_art_url {
-                                cached_art = match &art_url {
-                                    Some(url) => fetch_album_art(&http_client, url).await,
-                                    None => None,
-                                };
-                                cached_art_url = art_url;
+                            if art.update(&http_client, pb.album_art_url.as_deref()).await {
+                                store_album_art(&store, &art).await;
                             }
 
                             // Clone queue from previous state BEFORE calling send() to
                             // avoid a deadlock: borrow() holds a read lock and
                             // send() needs a write lock on the same RwLock.
                             let prev_queue = state_tx.borrow().player.queue.clone();
-                            let _ = state_tx.send(SpotifyState {
-                                player: PlayerState {
-                                    is_playing: pb.is_playing,
-                                    track_name: pb.track_name,
-                                    artist_name: pb.artist_name,
-                                    album_name: pb.album_name,
-                                    progress_ms: pb.progress_ms,
-                                    duration_ms: pb.duration_ms,
-                                    album_art_url: pb.album_art_url,
-                                    album_art: cached_art.clone(),
-                                    last_updated: Some(chrono::Utc::now()),
-                                    queue: prev_queue,
-                                },
-                                connected: true,
-                                error: None,
+                            let player = player_state(pb, &art, prev_queue);
+                            let delay = poll_delay(poll_interval, Some(&player));
+                            store_now_playing(&store, &player, &mut stored_track).await;
+                            state_tx.send_modify(|s| {
+                                s.player = player;
+                                s.connected = true;
+                                s.error = None;
                             });
+                            // A skip changes the queue; refresh it on the next poll
+                            last_queue_fetch = None;
+                            next_poll.as_mut().reset(Instant::now() + delay);
                         }
                     }
                 }
@@ -172,6 +259,328 @@ impl SpotifyPoller {
     }
 }
 
+/// Sleep timer, schedules and any fade-out in progress
+struct Automation {
+    sleep: Option<SleepTimer>,
+    schedules: Vec<PlaybackSchedule>,
+    /// Local date each schedule last fired on
+    last_fired: Vec<Option<chrono::NaiveDate>>,
+    fade: Option<(FadeOut, Instant)>,
+    fade_duration: Duration,
+}
+
+impl Automation {
+    fn new(settings: &SpotifySettings) -> Self {
+        let schedules: Vec<PlaybackSchedule> = settings
+            .schedules
+            .iter()
+            .filter_map(|entry| match entry.parse() {
+                Ok(s) => Some(s),
+                Err(e) => {
+                    warn!("Ignoring Spotify schedule {:?}: {}", entry, e);
+                    None
+                }
+            })
+            .collect();
+        if !schedules.is_empty() {
+            info!("Loaded {} Spotify schedule(s)", schedules.len());
+        }
+        Self {
+            sleep: None,
+            last_fired: vec![None; schedules.len()],
+            schedules,
+            fade: None,
+            fade_duration: Duration::from_secs(settings.fade_secs),
+        }
+    }
+
+    /// Fire whatever is due. Returns true if playback was changed.
+    async fn run(&mut self, client: &mut SpotifyClient, player: &PlayerState) -> bool {
+        let mut changed = self.step_fade(client).await;
+
+        let now = chrono::Utc::now();
+        if let Some(mode) = self
+            .sleep
+            .as_ref()
+            .filter(|t| t.is_due(player, now))
+            .map(|t| t.mode)
+        {
+            info!("Sleep timer finished");
+            self.sleep = None;
+            if player.is_playing {
+                changed |= self.stop(client, player, mode).await;
+            }
+        }
+
+        let local = chrono::Local::now().naive_local();
+        for i in 0..self.schedules.len() {
+            if !self.schedules[i].is_due(local, self.last_fired[i]) {
+                continue;
+            }
+            self.last_fired[i] = Some(local.date());
+            let action = self.schedules[i].action;
+            info!("Running Spotify schedule: {:?}", action);
+            changed |= match action {
+                ScheduleAction::Pause if player.is_playing => {
+                    self.stop(client, player, StopMode::Stop).await
+                }
+                ScheduleAction::Fade if player.is_playing => {
+                    self.stop(client, player, StopMode::Fade).await
+                }
+                ScheduleAction::Play if !player.is_playing => {
+                    log_err(client.toggle_playback(false).await)
+                }
+                _ => false,
+            };
+        }
+        changed
+    }
+
+    /// Pause now, or start a fade-out when the device reports its volume
+    async fn stop(
+        &mut self,
+        client: &mut SpotifyClient,
+        player: &PlayerState,
+        mode: StopMode,
+    ) -> bool {
+        match (mode, player.volume_percent) {
+            (StopMode::Fade, Some(volume)) if volume > 0 && self.fade.is_none() => {
+                self.fade = Some((
+                    FadeOut {
+                        start_volume: volume,
+                        duration: self.fade_duration,
+                    },
+                    Instant::now(),
+                ));
+                false
+            }
+            (StopMode::Fade, _) if self.fade.is_some() => false,
+            _ => log_err(client.toggle_playback(true).await),
+        }
+    }
+
+    /// Advance the fade; once silent, pause and restore the original volume
+    async fn step_fade(&mut self, client: &mut SpotifyClient) -> bool {
+        let Some((fade, started)) = self.fade else {
+            return false;
+        };
+        let volume = fade.volume_at(started.elapsed());
+        if volume > 0 {
+            log_err(client.set_volume(volume).await);
+            return false;
+        }
+        self.fade = None;
+        let paused = log_err(client.toggle_playback(true).await);
+        log_err(client.set_volume(fade.start_volume).await);
+        paused
+    }
+
+    /// Abort a fade (e.g. the user pressed play/pause) and restore the volume
+    async fn cancel_fade(&mut self, client: &mut SpotifyClient) {
+        if let Some((fade, _)) = self.fade.take() {
+            log_err(client.set_volume(fade.start_volume).await);
+        }
+    }
+
+    /// Push the timer/fade status to the UI if it changed
+    fn publish(&self, state_tx: &watch::Sender<SpotifyState>) {
+        state_tx.send_if_modified(|s| {
+            let fading = self.fade.is_some();
+            if s.sleep_timer == self.sleep && s.fading == fading {
+                return false;
+            }
+            s.sleep_timer = self.sleep.clone();
+            s.fading = fading;
+            true
+        });
+    }
+}
+
+/// Log a failed automation request; returns whether it succeeded
+fn log_err(result: anyhow::Result<()>) -> bool {
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            warn!("Spotify automation request failed: {}", e);
+            false
+        }
+    }
+}
+
+/// How long to wait before the next poll after a successful response.
+/// Polls at `active` while playing (or right after the track ends, if sooner),
+/// and slows down when paused or when no device is active.
+fn poll_delay(active: Duration, player: Option<&PlayerState>) -> Duration {
+    match player {
+        None => IDLE_POLL.max(active),
+        Some(p) if !p.is_playing || p.track_name.is_empty() => PAUSED_POLL.max(active),
+        Some(p) => {
+            // Pick up the next track promptly instead of waiting a full interval
+            let until_end = Duration::from_millis(p.remaining_ms() + 500);
+            active.min(until_end).max(Duration::from_secs(1))
+        }
+    }
+}
+
+/// How long to wait after a failed poll: honour `Retry-After` on 429,
+/// otherwise back off exponentially with the number of consecutive failures.
+fn error_delay(active: Duration, failures: u32, err: &anyhow::Error) -> Duration {
+    let backoff = active
+        .max(Duration::from_secs(2))
+        .saturating_mul(2u32.saturating_pow(failures.min(8)))
+        .min(MAX_BACKOFF);
+    match err.downcast_ref::<RateLimited>() {
+        Some(rl) => rl.retry_after,
+        None => backoff,
+    }
+}
+
+/// Absolute seek position for a relative skip, clamped to the current item
+fn seek_target(player: &PlayerState, offset_ms: i64) -> u64 {
+    let target = (player.current_progress_ms() as i64 + offset_ms).max(0) as u64;
+    if player.duration_ms > 0 {
+        target.min(player.duration_ms.saturating_sub(1000))
+    } else {
+        target
+    }
+}
+
+/// Build the displayed player state from a playback response
+fn player_state(pb: PlaybackResponse, art: &ArtCache, queue: Vec<QueueItem>) -> PlayerState {
+    PlayerState {
+        is_playing: pb.is_playing,
+        track_name: pb.track_name,
+        artist_name: pb.artist_name,
+        album_name: pb.album_name,
+        progress_ms: pb.progress_ms,
+        duration_ms: pb.duration_ms,
+        album_art_url: pb.album_art_url,
+        album_art: art.art.clone(),
+        last_updated: Some(chrono::Utc::now()),
+        queue,
+        volume_percent: pb.volume_percent,
+        kind: pb.kind,
+        episode: pb.episode,
+    }
+}
+
+/// Album art for the most recently seen image URL
+#[derive(Default)]
+struct ArtCache {
+    url: Option<String>,
+    art: Option<AlbumArt>,
+}
+
+impl ArtCache {
+    /// Fetch album art if the URL changed. Returns true if it did.
+    async fn update(&mut self, client: &reqwest::Client, url: Option<&str>) -> bool {
+        if url == self.url.as_deref() {
+            return false;
+        }
+        self.art = match url {
+            Some(url) => fetch_album_art(client, url).await,
+            None => None,
+        };
+        self.url = url.map(|s| s.to_string());
+        true
+    }
+}
+
+/// Identifies a track for deciding when to store now-playing info again
+fn track_key(player: &PlayerState) -> (String, String) {
+    (player.track_name.clone(), player.artist_name.clone())
+}
+
+/// Store now-playing info when the track changes (not on every poll, to
+/// spare the SD card)
+async fn store_now_playing(
+    store: &Store,
+    player: &PlayerState,
+    stored: &mut Option<(String, String)>,
+) {
+    let key = track_key(player);
+    if stored.as_ref() == Some(&key) {
+        return;
+    }
+    match store.save_json(Section::Spotify, player).await {
+        Ok(()) => *stored = Some(key),
+        Err(e) => warn!("Failed to store now playing: {}", e),
+    }
+}
+
+/// Store the current album art as PNG with the URL it came from, or
+/// delete the stored art when there is none. The URL is removed first and
+/// written last, so art left half-replaced by a crash is never restored.
+async fn store_album_art(store: &Store, art: &ArtCache) {
+    if let Err(e) = store.remove(Section::AlbumArtSource).await {
+        warn!("Failed to remove stored album art source: {}", e);
+        return;
+    }
+    let Some(ref art) = art.art else {
+        if let Err(e) = store.remove(Section::AlbumArt).await {
+            warn!("Failed to remove stored album art: {}", e);
+        }
+        return;
+    };
+    let Some(image) = image::RgbaImage::from_raw(art.width, art.height, art.rgba_data.clone())
+    else {
+        return;
+    };
+    let mut png = std::io::Cursor::new(Vec::new());
+    if let Err(e) = image.write_to(&mut png, image::ImageFormat::Png) {
+        warn!("Failed to encode album art: {}", e);
+        return;
+    }
+    let stored = match store.write(Section::AlbumArt, png.get_ref()).await {
+        Ok(()) => {
+            store
+                .write(Section::AlbumArtSource, art.source_url.as_bytes())
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = stored {
+        warn!("Failed to store album art: {}", e);
+    }
+}
+
+/// The last stored track with its album art, shown paused since playback
+/// has moved on since it was stored. Seeds `art` so the image isn't
+/// downloaded again while the track is unchanged.
+async fn restore_now_playing(store: &Store, art: &mut ArtCache) -> Option<PlayerState> {
+    let mut player: PlayerState = match store.load_json(Section::Spotify).await {
+        Ok(player) => player?,
+        Err(e) => {
+            warn!("Failed to load stored now playing: {}", e);
+            return None;
+        }
+    };
+    player.is_playing = false;
+
+    let Some(url) = player.album_art_url.clone() else {
+        return Some(player);
+    };
+    // Art stored for another track (or without a source) is left alone
+    let source = store.read(Section::AlbumArtSource).await.ok().flatten();
+    if source.as_deref() != Some(url.as_bytes()) {
+        return Some(player);
+    }
+    let png = store.read(Section::AlbumArt).await.ok().flatten();
+    if let Some(image) = png.and_then(|png| image::load_from_memory(&png).ok()) {
+        let rgba = image.to_rgba8();
+        let restored = AlbumArt {
+            width: rgba.width(),
+            height: rgba.height(),
+            rgba_data: rgba.into_raw(),
+            source_url: url.clone(),
+        };
+        player.album_art = Some(restored.clone());
+        art.art = Some(restored);
+        art.url = Some(url);
+    }
+    Some(player)
+}
+
 /// Fetch album art from a URL and decode it into RGBA pixel data
 async fn fetch_album_art(client: &reqwest::Client, url: &str) -> Option<AlbumArt> {
     let resp = client.get(url).send().await.ok()?;
@@ -191,3 +600,151 @@ async fn fetch_album_art(client: &reqwest::Client, url: &str) -> Option<AlbumArt
         source_url: url.to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_now_playing_restored_paused() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path());
+        let art = ArtCache {
+            url: Some("https://i.scdn.co/image/abc".to_string()),
+            art: Some(AlbumArt {
+                width: 2,
+                height: 1,
+                rgba_data: vec![255, 0, 0, 255, 0, 0, 255, 255],
+                source_url: "https://i.scdn.co/image/abc".to_string(),
+            }),
+        };
+        let player = PlayerState {
+            is_playing: true,
+            track_name: "Song".to_string(),
+            artist_name: "Artist".to_string(),
+            album_art_url: art.url.clone(),
+            ..Default::default()
+        };
+        store_album_art(&store, &art).await;
+        let mut stored = None;
+        store_now_playing(&store, &player, &mut stored).await;
+        assert_eq!(stored, Some(track_key(&player)));
+
+        let mut restored_art = ArtCache::default();
+        let restored = restore_now_playing(&store, &mut restored_art)
+            .await
+            .unwrap();
+        assert_eq!(restored.track_name, "Song");
+        assert!(!restored.is_playing);
+        let image = restored.album_art.unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.rgba_data, art.art.unwrap().rgba_data);
+        assert_eq!(restored_art.url, art.url);
+    }
+
+    #[tokio::test]
+    async fn test_stored_art_must_match_track() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path());
+        let old_url = "https://i.scdn.co/image/abc".to_string();
+        let mut art = ArtCache {
+            url: Some(old_url.clone()),
+            art: Some(AlbumArt {
+                width: 1,
+                height: 1,
+                rgba_data: vec![255, 0, 0, 255],
+                source_url: old_url.clone(),
+            }),
+        };
+        store_album_art(&store, &art).await;
+
+        // Stored art of another track isn't shown for this one
+        let mut player = PlayerState {
+            track_name: "Next".to_string(),
+            album_art_url: Some(format!("{}/image/def", server.uri())),
+            ..Default::default()
+        };
+        store_now_playing(&store, &player, &mut None).await;
+        let restored = restore_now_playing(&store, &mut ArtCache::default())
+            .await
+            .unwrap();
+        assert!(restored.album_art.is_none());
+
+        // The cover of the next track can't be fetched: the old art goes
+        let new_url = player.album_art_url.clone();
+        assert!(
+            art.update(&reqwest::Client::new(), new_url.as_deref())
+                .await
+        );
+        assert!(art.art.is_none());
+        store_album_art(&store, &art).await;
+        assert!(store.read(Section::AlbumArt).await.unwrap().is_none());
+        assert!(store.read(Section::AlbumArtSource).await.unwrap().is_none());
+
+        // Back on the old track, nothing stale is restored either
+        player.album_art_url = Some(old_url);
+        store_now_playing(&store, &player, &mut None).await;
+        let restored = restore_now_playing(&store, &mut ArtCache::default())
+            .await
+            .unwrap();
+        assert!(restored.album_art.is_none());
+    }
+
+    #[test]
+    fn test_poll_delay_adapts_to_playback() {
+        let active = Duration::from_secs(5);
+        assert_eq!(poll_delay(active, None), IDLE_POLL);
+
+        let mut player = PlayerState {
+            track_name: "Song".to_string(),
+            duration_ms: 200_000,
+            progress_ms: 10_000,
+            last_updated: Some(chrono::Utc::now()),
+            ..Default::default()
+        };
+        assert_eq!(poll_delay(active, Some(&player)), PAUSED_POLL);
+
+        player.is_playing = true;
+        assert_eq!(poll_delay(active, Some(&player)), active);
+
+        // Close to the end of the track: poll right after it finishes
+        player.progress_ms = 198_000;
+        let delay = poll_delay(active, Some(&player));
+        assert!(delay < active && delay >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_seek_target_clamps() {
+        let player = PlayerState {
+            progress_ms: 10_000,
+            duration_ms: 60_000,
+            ..Default::default()
+        };
+        assert_eq!(seek_target(&player, SKIP_BACK_MS), 0);
+        assert_eq!(seek_target(&player, SKIP_FORWARD_MS), 40_000);
+        assert_eq!(seek_target(&player, 120_000), 59_000);
+    }
+
+    #[test]
+    fn test_error_delay_backoff() {
+        let active = Duration::from_secs(5);
+        let err = anyhow::anyhow!("boom");
+        assert_eq!(error_delay(active, 1, &err), Duration::from_secs(10));
+        assert_eq!(error_delay(active, 2, &err), Duration::from_secs(20));
+        assert_eq!(error_delay(active, 20, &err), MAX_BACKOFF);
+
+        let limited: anyhow::Error = RateLimited {
+            retry_after: Duration::from_secs(30),
+        }
+        .into();
+        assert_eq!(error_delay(active, 1, &limited), Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,331 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::state::PlayerState;
+
+/// How long after its start time a schedule may still fire (covers slow polls)
+const SCHEDULE_GRACE_MINS: i64 = 2;
+
+/// Sleep timer lengths cycled through from the keyboard
+const SLEEP_PRESETS_MINS: [u32; 3] = [15, 30, 60];
+
+/// How playback is stopped when a sleep timer runs out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StopMode {
+    /// Pause immediately
+    #[default]
+    Stop,
+    /// Lower the volume gradually, then pause and restore the volume
+    Fade,
+}
+
+/// Spotify automation settings (persisted in settings.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifySettings {
+    /// What the sleep timer does when it runs out
+    #[serde(default)]
+    pub sleep_mode: StopMode,
+    /// Length of the volume fade in seconds
+    #[serde(default = "default_fade_secs")]
+    pub fade_secs: u64,
+    /// Recurring playback actions, e.g. pause at 18:00 on weekdays
+    #[serde(default)]
+    pub schedules: Vec<ScheduleEntry>,
+}
+
+impl Default for SpotifySettings {
+    fn default() -> Self {
+        Self {
+            sleep_mode: StopMode::default(),
+            fade_secs: default_fade_secs(),
+            schedules: Vec::new(),
+        }
+    }
+}
+
+fn default_fade_secs() -> u64 {
+    30
+}
+
+/// Action performed by a schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleAction {
+    Pause,
+    Fade,
+    Play,
+}
+
+/// A schedule as written in settings.json:
+/// `{ "action": "pause", "at": "18:00", "days": "weekdays" }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub action: ScheduleAction,
+    /// Local time, "HH:MM"
+    pub at: String,
+    /// "daily", "weekdays", "weekends", or a list such as "mon,wed,fri" / "mon-thu"
+    #[serde(default = "default_days")]
+    pub days: String,
+}
+
+fn default_days() -> String {
+    "daily".to_string()
+}
+
+impl ScheduleEntry {
+    /// Validate the entry into a runnable schedule
+    pub fn parse(&self) -> Result<PlaybackSchedule> {
+        let time = NaiveTime::parse_from_str(self.at.trim(), "%H:%M")
+            .map_err(|_| anyhow!("invalid time '{}', expected HH:MM", self.at))?;
+        Ok(PlaybackSchedule {
+            action: self.action,
+            time,
+            days: parse_days(&self.days)?,
+        })
+    }
+}
+
+/// A validated recurring playback action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybackSchedule {
+    pub action: ScheduleAction,
+    pub time: NaiveTime,
+    /// Enabled days, indexed from Monday
+    pub days: [bool; 7],
+}
+
+impl PlaybackSchedule {
+    /// Whether the schedule should fire at local time `now`, given the date
+    /// it last fired on (it fires at most once per day)
+    pub fn is_due(&self, now: NaiveDateTime, last_fired: Option<NaiveDate>) -> bool {
+        let today = now.date();
+        if last_fired == Some(today) || !self.days[today.weekday().num_days_from_monday() as usize]
+        {
+            return false;
+        }
+        let start = today.and_time(self.time);
+        now >= start && now < start + Duration::minutes(SCHEDULE_GRACE_MINS)
+    }
+}
+
+/// Parse a day specification into a Monday-indexed mask
+fn parse_days(spec: &str) -> Result<[bool; 7]> {
+    let spec = spec.trim().to_lowercase();
+    match spec.as_str() {
+        "" | "daily" | "everyday" => return Ok([true; 7]),
+        "weekdays" => return Ok([true, true, true, true, true, false, false]),
+        "weekends" => return Ok([false, false, false, false, false, true, true]),
+        _ => {}
+    }
+
+    let mut days = [false; 7];
+    for part in spec.split(',').map(str::trim) {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (day_index(from)?, day_index(to)?);
+                let mut d = from;
+                loop {
+                    days[d] = true;
+                    if d == to {
+                        break;
+                    }
+                    d = (d + 1) % 7;
+                }
+            }
+            None => days[day_index(part)?] = true,
+        }
+    }
+    Ok(days)
+}
+
+fn day_index(name: &str) -> Result<usize> {
+    let idx = match name.trim().get(..3).unwrap_or("") {
+        "mon" => 0,
+        "tue" => 1,
+        "wed" => 2,
+        "thu" => 3,
+        "fri" => 4,
+        "sat" => 5,
+        "sun" => 6,
+        _ => return Err(anyhow!("unknown day '{}'", name)),
+    };
+    Ok(idx)
+}
+
+/// When a sleep timer runs out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SleepTrigger {
+    At(DateTime<Utc>),
+    /// At the end of the item that was playing when the timer was set
+    EndOfTrack,
+}
+
+/// An armed sleep timer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SleepTimer {
+    pub trigger: SleepTrigger,
+    pub mode: StopMode,
+    /// Track playing when the timer was armed (for end-of-track timers)
+    pub track_name: String,
+}
+
+impl SleepTimer {
+    /// Next timer in the keyboard cycle: off -> 15 -> 30 -> 60 min -> end of track -> off
+    pub fn next_preset(
+        current: Option<&SleepTimer>,
+        mode: StopMode,
+        player: &PlayerState,
+        now: DateTime<Utc>,
+    ) -> Option<SleepTimer> {
+        let minutes_left = |t: &SleepTimer| match t.trigger {
+            SleepTrigger::At(at) => Some((at - now).num_minutes().max(0) as u32),
+            SleepTrigger::EndOfTrack => None,
+        };
+        let next = match current {
+            None => Some(SLEEP_PRESETS_MINS[0]),
+            Some(t) => match minutes_left(t) {
+                // Step to the next preset longer than what is left
+                Some(left) => SLEEP_PRESETS_MINS.iter().copied().find(|m| *m > left + 1),
+                None => return None,
+            },
+        };
+
+        Some(SleepTimer {
+            trigger: match next {
+                Some(mins) => SleepTrigger::At(now + Duration::minutes(mins as i64)),
+                None => SleepTrigger::EndOfTrack,
+            },
+            mode,
+            track_name: player.track_name.clone(),
+        })
+    }
+
+    /// Whether the timer has run out for the given (interpolated) player state
+    pub fn is_due(&self, player: &PlayerState, now: DateTime<Utc>) -> bool {
+        match self.trigger {
+            SleepTrigger::At(at) => now >= at,
+            SleepTrigger::EndOfTrack => {
+                player.track_name != self.track_name
+                    || (player.duration_ms > 0 && player.remaining_ms() < 1500)
+            }
+        }
+    }
+
+    /// Short label for the player widgets, e.g. "23m" or "end of track"
+    pub fn label(&self, now: DateTime<Utc>) -> String {
+        match self.trigger {
+            SleepTrigger::At(at) => {
+                let secs = (at - now).num_seconds().max(0);
+                if secs >= 60 {
+                    format!("{}m", (secs + 59) / 60)
+                } else {
+                    format!("{}s", secs)
+                }
+            }
+            SleepTrigger::EndOfTrack => "end of track".to_string(),
+        }
+    }
+}
+
+/// A volume fade in progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FadeOut {
+    pub start_volume: u32,
+    pub duration: std::time::Duration,
+}
+
+impl FadeOut {
+    /// Volume to set `elapsed` into the fade (linear ramp to zero)
+    pub fn volume_at(&self, elapsed: std::time::Duration) -> u32 {
+        if self.duration.is_zero() || elapsed >= self.duration {
+            return 0;
+        }
+        let left = 1.0 - elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        (self.start_volume as f64 * left).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(
+            parse_days("weekdays").unwrap(),
+            [true, true, true, true, true, false, false]
+        );
+        assert_eq!(
+            parse_days("mon,wed").unwrap(),
+            [true, false, true, false, false, false, false]
+        );
+        assert_eq!(
+            parse_days("fri-mon").unwrap(),
+            [true, false, false, false, true, true, true]
+        );
+        assert!(parse_days("funday").is_err());
+    }
+
+    #[test]
+    fn test_schedule_is_due() {
+        let schedule = ScheduleEntry {
+            action: ScheduleAction::Pause,
+            at: "18:00".to_string(),
+            days: "weekdays".to_string(),
+        }
+        .parse()
+        .unwrap();
+
+        // 2026-10-16 is a Friday, 2026-10-17 a Saturday
+        assert!(!schedule.is_due(at("2026-10-16", "17:59"), None));
+        assert!(schedule.is_due(at("2026-10-16", "18:00"), None));
+        assert!(schedule.is_due(at("2026-10-16", "18:01"), None));
+        assert!(!schedule.is_due(at("2026-10-16", "18:05"), None));
+        assert!(!schedule.is_due(at("2026-10-17", "18:00"), None));
+
+        let friday = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        assert!(!schedule.is_due(at("2026-10-16", "18:01"), Some(friday)));
+    }
+
+    #[test]
+    fn test_sleep_timer_presets() {
+        let now = Utc::now();
+        let player = PlayerState {
+            track_name: "Song".to_string(),
+            ..Default::default()
+        };
+        let t1 = SleepTimer::next_preset(None, StopMode::Stop, &player, now).unwrap();
+        assert_eq!(t1.trigger, SleepTrigger::At(now + Duration::minutes(15)));
+        let t2 = SleepTimer::next_preset(Some(&t1), StopMode::Stop, &player, now).unwrap();
+        assert_eq!(t2.trigger, SleepTrigger::At(now + Duration::minutes(30)));
+        let t3 = SleepTimer::next_preset(Some(&t2), StopMode::Stop, &player, now).unwrap();
+        let t4 = SleepTimer::next_preset(Some(&t3), StopMode::Stop, &player, now).unwrap();
+        assert_eq!(t4.trigger, SleepTrigger::EndOfTrack);
+        assert!(SleepTimer::next_preset(Some(&t4), StopMode::Stop, &player, now).is_none());
+
+        // End-of-track fires once the track changes
+        let next = PlayerState {
+            track_name: "Other".to_string(),
+            ..Default::default()
+        };
+        assert!(!t4.is_due(&player, now));
+        assert!(t4.is_due(&next, now));
+    }
+
+    #[test]
+    fn test_fade_volume() {
+        let fade = FadeOut {
+            start_volume: 80,
+            duration: std::time::Duration::from_secs(20),
+        };
+        assert_eq!(fade.volume_at(std::time::Duration::ZERO), 80);
+        assert_eq!(fade.volume_at(std::time::Duration::from_secs(10)), 40);
+        assert_eq!(fade.volume_at(std::time::Duration::from_secs(25)), 0);
+    }
+}
@@ -1,16 +1,22 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::schedule::SleepTimer;
+
 /// Spotify playback state
 #[derive(Debug, Clone, Default)]
 pub struct SpotifyState {
     pub player: PlayerState,
     pub connected: bool,
     pub error: Option<String>,
+    /// Armed sleep timer, if any
+    pub sleep_timer: Option<SleepTimer>,
+    /// Whether a volume fade-out is in progress
+    pub fading: bool,
 }
 
-/// Current player state from Spotify
-#[derive(Debug, Clone, Default)]
+/// Current player state from Spotify (persisted in the store, minus the art)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PlayerState {
     pub is_playing: bool,
     pub track_name: String,
@@ -21,13 +27,51 @@ pub struct PlayerState {
     pub album_art_url: Option<String>,
     pub last_updated: Option<DateTime<Utc>>,
     /// Cached album art as raw RGBA pixels (width, height, data)
+    #[serde(skip)]
     pub album_art: Option<AlbumArt>,
     /// Up next queue (up to 5 items)
     pub queue: Vec<QueueItem>,
+    /// Volume of the active device
+    pub volume_percent: Option<u32>,
+    /// Whether the current item is a track, podcast episode, ad, ...
+    pub kind: MediaKind,
+    /// Podcast details when `kind` is [`MediaKind::Episode`]
+    pub episode: Option<EpisodeInfo>,
+}
+
+/// Type of the currently playing item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaKind {
+    #[default]
+    Track,
+    Episode,
+    /// Ads and anything else Spotify does not describe
+    Other,
+}
+
+impl MediaKind {
+    /// Parse Spotify's `type` / `currently_playing_type` value
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "track" => Self::Track,
+            "episode" => Self::Episode,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Podcast episode details
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpisodeInfo {
+    pub show_name: String,
+    pub publisher: String,
+    /// Where the user left off last time, as saved by Spotify
+    pub resume_position_ms: Option<u64>,
+    pub fully_played: bool,
 }
 
 /// A track/episode in the queue (display only)
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QueueItem {
     pub name: String,
     pub artist: String,
@@ -44,12 +88,55 @@ pub struct AlbumArt {
 }
 
 impl PlayerState {
+    /// Playback position interpolated from the last poll, so the progress
+    /// bar keeps moving between (infrequent) API requests
+    pub fn current_progress_ms(&self) -> u64 {
+        self.progress_at(Utc::now())
+    }
+
+    fn progress_at(&self, now: DateTime<Utc>) -> u64 {
+        let elapsed = match self.last_updated {
+            Some(at) if self.is_playing => (now - at).num_milliseconds().max(0) as u64,
+            _ => 0,
+        };
+        let progress = self.progress_ms + elapsed;
+        if self.duration_ms > 0 {
+            progress.min(self.duration_ms)
+        } else {
+            progress
+        }
+    }
+
+    /// Milliseconds left in the current item (interpolated)
+    pub fn remaining_ms(&self) -> u64 {
+        self.duration_ms.saturating_sub(self.current_progress_ms())
+    }
+
     /// Progress as a fraction (0.0 - 1.0)
     pub fn progress_fraction(&self) -> f64 {
         if self.duration_ms == 0 {
             return 0.0;
         }
-        self.progress_ms as f64 / self.duration_ms as f64
+        self.current_progress_ms() as f64 / self.duration_ms as f64
+    }
+
+    /// Message shown when there is no item to display
+    pub fn idle_message(&self) -> &'static str {
+        if self.is_playing && self.kind == MediaKind::Other {
+            "Advertisement playing"
+        } else {
+            "No track playing"
+        }
+    }
+
+    /// Status line for podcast episodes, e.g. "Podcast · resume 12:34"
+    pub fn episode_label(&self) -> Option<String> {
+        let ep = self.episode.as_ref()?;
+        Some(match ep.resume_position_ms {
+            _ if ep.fully_played => "Podcast · played".to_string(),
+            Some(ms) if ms > 0 => format!("Podcast · resume {}", Self::format_time(ms)),
+            _ => "Podcast".to_string(),
+        })
     }
 
     /// Format time as M:SS
@@ -75,3 +162,29 @@ impl SpotifyTokens {
         Utc::now() >= self.expires_at
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_interpolation() {
+        let now = Utc::now();
+        let mut player = PlayerState {
+            is_playing: true,
+            progress_ms: 10_000,
+            duration_ms: 60_000,
+            last_updated: Some(now - chrono::Duration::seconds(4)),
+            ..Default::default()
+        };
+        assert_eq!(player.progress_at(now), 14_000);
+
+        // Clamped to the track length
+        player.last_updated = Some(now - chrono::Duration::seconds(120));
+        assert_eq!(player.progress_at(now), 60_000);
+
+        // Paused playback does not advance
+        player.is_playing = false;
+        assert_eq!(player.progress_at(now), 10_000);
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// `<file>.<suffix>` next to `path`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// The previous good copy kept by `write_atomic`
+pub fn previous_path(path: &Path) -> PathBuf {
+    sibling(path, "prev")
+}
+
+/// Replace `path` so that a power cut at any point leaves either the old
+/// or the new contents on disk, never a mix. The data is synced to a
+/// temporary file before it's renamed into place, and the file it replaces
+/// is kept as `<file>.prev` for `read_with_fallback`.
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+
+    let tmp = sibling(path, "tmp");
+    let mut file = File::create(&tmp).with_context(|| format!("creating {:?}", tmp))?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    drop(file);
+
+    if path.exists() {
+        fs::rename(path, previous_path(path))?;
+    }
+    fs::rename(&tmp, path)?;
+    sync_dir(dir)
+}
+
+/// Make renames in `dir` durable
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> Result<()> {
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Read and parse `path`, falling back to the copy `write_atomic` kept when
+/// the file is missing or doesn't parse (e.g. truncated by a crash on a
+/// filesystem that doesn't order writes). None if neither file exists.
+pub fn read_with_fallback<T>(path: &Path, parse: impl Fn(&[u8]) -> Result<T>) -> Result<Option<T>> {
+    let current = match fs::read(path) {
+        Ok(data) => Some(parse(&data).with_context(|| format!("reading {:?}", path))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(Ok(value)) = current {
+        return Ok(Some(value));
+    }
+
+    let previous = previous_path(path);
+    match (current, fs::read(&previous)) {
+        (current, Ok(data)) => match parse(&data) {
+            Ok(value) => {
+                if let Some(Err(e)) = current {
+                    warn!("{:#}, using the previous copy", e);
+                }
+                Ok(Some(value))
+            }
+            Err(e) => match current {
+                Some(Err(current_err)) => Err(current_err),
+                _ => Err(e.context(format!("reading {:?}", previous))),
+            },
+        },
+        (Some(Err(e)), Err(_)) => Err(e),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn parse(data: &[u8]) -> Result<Vec<u32>> {
+        Ok(serde_json::from_slice(data)?)
+    }
+
+    #[test]
+    fn test_truncated_file_falls_back() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        assert!(read_with_fallback(&path, parse).unwrap().is_none());
+
+        write_atomic(&path, b"[1, 2]").unwrap();
+        write_atomic(&path, b"[1, 2, 3]").unwrap();
+        assert_eq!(
+            read_with_fallback(&path, parse).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert!(!sibling(&path, "tmp").exists());
+
+        // Cut off mid-write: the previous copy is used
+        std::fs::write(&path, b"[1, 2,").unwrap();
+        assert_eq!(read_with_fallback(&path, parse).unwrap(), Some(vec![1, 2]));
+
+        // Crashed between the two renames
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read_with_fallback(&path, parse).unwrap(), Some(vec![1, 2]));
+
+        // Nothing good left
+        std::fs::write(&path, b"").unwrap();
+        std::fs::write(previous_path(&path), b"[").unwrap();
+        assert!(read_with_fallback(&path, parse).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::debug;
+
+use super::atomic::{previous_path, read_with_fallback, write_atomic};
+
+/// What each poller persists, one file per section in the store directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    /// GitHub data (see `GithubCache`, which versions it)
+    Github,
+    /// Star, fork and follower history
+    History,
+    /// Last fetched news headlines
+    News,
+    /// Last known now-playing info (`spotify.json` beside it holds the
+    /// Spotify tokens)
+    Spotify,
+    /// Album art of the last known track, as PNG
+    AlbumArt,
+    /// URL the stored album art came from, so it's only restored for the
+    /// track it belongs to
+    AlbumArtSource,
+}
+
+impl Section {
+    fn file_name(&self) -> &'static str {
+        match self {
+            Self::Github => "cache.json",
+            Self::History => "history.json",
+            Self::News => "news.json",
+            Self::Spotify => "now_playing.json",
+            Self::AlbumArt => "album_art.png",
+            Self::AlbumArtSource => "album_art.url",
+        }
+    }
+}
+
+/// On-disk store shared by every poller, so the dashboard comes up with
+/// the last known data after a reboot
+#[derive(Debug, Clone)]
+pub struct Store {
+    dir: PathBuf,
+}
+
+impl Store {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// File backing a section
+    pub fn path(&self, section: Section) -> PathBuf {
+        self.dir.join(section.file_name())
+    }
+
+    /// Raw contents of a section, or None if it was never written. Unlike
+    /// `load_with`, this doesn't fall back to the previous copy.
+    pub async fn read(&self, section: Section) -> Result<Option<Vec<u8>>> {
+        let path = self.path(section);
+        if !path.exists() {
+            debug!("Nothing stored at {:?}", path);
+            return Ok(None);
+        }
+        Ok(Some(fs::read(&path).await?))
+    }
+
+    /// Parse a section, falling back to the previous copy if the current
+    /// one is damaged (see `read_with_fallback`)
+    pub async fn load_with<T, F>(&self, section: Section, parse: F) -> Result<Option<T>>
+    where
+        T: Send + 'static,
+        F: Fn(&[u8]) -> Result<T> + Send + 'static,
+    {
+        let path = self.path(section);
+        tokio::task::spawn_blocking(move || read_with_fallback(&path, parse)).await?
+    }
+
+    /// Replace a section crash-safely (see `write_atomic`)
+    pub async fn write(&self, section: Section, data: &[u8]) -> Result<()> {
+        let path = self.path(section);
+        let len = data.len();
+        let data = data.to_vec();
+        let target = path.clone();
+        tokio::task::spawn_blocking(move || write_atomic(&target, &data)).await??;
+        debug!("Stored {} bytes at {:?}", len, path);
+        Ok(())
+    }
+
+    /// Delete a section and the previous copy `write` kept, if stored
+    pub async fn remove(&self, section: Section) -> Result<()> {
+        let path = self.path(section);
+        for file in [previous_path(&path), path] {
+            if file.exists() {
+                fs::remove_file(&file).await?;
+                debug!("Removed {:?}", file);
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserialize a JSON section
+    pub async fn load_json<T: DeserializeOwned + Send + 'static>(
+        &self,
+        section: Section,
+    ) -> Result<Option<T>> {
+        self.load_with(section, |data| Ok(serde_json::from_slice(data)?))
+            .await
+    }
+
+    /// Serialize a section as JSON
+    pub async fn save_json<T: Serialize>(&self, section: Section, value: &T) -> Result<()> {
+        self.write(section, &serde_json::to_vec(value)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_sections_roundtrip() {
+        let dir = tempdir().unwrap();
+        let store = Store::new(dir.path().join("nested"));
+
+        assert!(store.read(Section::News).await.unwrap().is_none());
+        store
+            .save_json(Section::News, &vec!["headline"])
+            .await
+            .unwrap();
+        store.write(Section::AlbumArt, &[1, 2, 3]).await.unwrap();
+        // Overwriting keeps the previous copy and no temporary file
+        store
+            .save_json(Section::News, &vec!["newer"])
+            .await
+            .unwrap();
+
+        let news: Vec<String> = store.load_json(Section::News).await.unwrap().unwrap();
+        assert_eq!(news, ["newer"]);
+        assert_eq!(
+            store.read(Section::AlbumArt).await.unwrap().unwrap(),
+            [1, 2, 3]
+        );
+        let mut files: Vec<_> = std::fs::read_dir(dir.path().join("nested"))
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["album_art.png", "news.json", "news.json.prev"]);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod atomic;
+pub mod disk;
+
+pub use atomic::{read_with_fallback, write_atomic};
+pub use disk::{Section, Store};
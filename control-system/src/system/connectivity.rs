@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+/// How often to probe while online
+const ONLINE_CHECK: Duration = Duration::from_secs(15);
+/// How often to probe while offline, so sources refresh soon after the
+/// network comes back
+const OFFLINE_CHECK: Duration = Duration::from_secs(5);
+/// How long a probe may take before it counts as failed
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Failed probes in a row before going offline, so one dropped packet
+/// doesn't pause every poller
+const FAILURES_BEFORE_OFFLINE: u32 = 2;
+
+/// Whether the network is reachable, as seen by the connectivity monitor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connectivity {
+    pub online: bool,
+    /// When the current state began (None until it first changes)
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl Default for Connectivity {
+    /// Online until a probe says otherwise, so nothing waits at startup
+    fn default() -> Self {
+        Self {
+            online: true,
+            since: None,
+        }
+    }
+}
+
+/// Debounces probe results into `Connectivity`
+#[derive(Debug, Default)]
+struct Tracker {
+    state: Connectivity,
+    failures: u32,
+}
+
+impl Tracker {
+    /// Record a probe result. Returns true if the state changed.
+    fn record(&mut self, reachable: bool, now: DateTime<Utc>) -> bool {
+        self.failures = if reachable { 0 } else { self.failures + 1 };
+        let online = self.failures < FAILURES_BEFORE_OFFLINE;
+        if online == self.state.online {
+            return false;
+        }
+        self.state = Connectivity {
+            online,
+            since: Some(now),
+        };
+        true
+    }
+}
+
+/// Watches whether the network is reachable by opening a TCP connection
+/// to a probe address (`host:port`). Network pollers pause while offline
+/// and refresh as soon as it comes back.
+pub struct ConnectivityMonitor;
+
+impl ConnectivityMonitor {
+    pub fn start(probe: String) -> watch::Receiver<Connectivity> {
+        let (tx, rx) = watch::channel(Connectivity::default());
+
+        tokio::spawn(async move {
+            let mut tracker = Tracker::default();
+            loop {
+                let reachable = probe_once(&probe).await;
+                if tracker.record(reachable, Utc::now()) {
+                    if tracker.state.online {
+                        info!("Network is back, resuming pollers");
+                    } else {
+                        warn!("Network unreachable ({}), pausing pollers", probe);
+                    }
+                    if tx.send(tracker.state.clone()).is_err() {
+                        break;
+                    }
+                }
+                let delay = if tracker.state.online {
+                    ONLINE_CHECK
+                } else {
+                    OFFLINE_CHECK
+                };
+                tokio::time::sleep(delay).await;
+            }
+        });
+
+        rx
+    }
+}
+
+/// Whether a TCP connection to `probe` (resolving it if needed) succeeds
+async fn probe_once(probe: &str) -> bool {
+    match tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(probe)).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            debug!("Connectivity probe to {} failed: {}", probe, e);
+            false
+        }
+        Err(_) => {
+            debug!("Connectivity probe to {} timed out", probe);
+            false
+        }
+    }
+}
+
+/// Wait until the connectivity state changes to online. Pending forever
+/// if the monitor has stopped, so it can sit in a `select!` loop.
+pub async fn back_online(rx: &mut watch::Receiver<Connectivity>) {
+    loop {
+        if rx.changed().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+        if rx.borrow_and_update().online {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_after_repeated_failures() {
+        let now = Utc::now();
+        let mut tracker = Tracker::default();
+
+        assert!(!tracker.record(true, now));
+        // One dropped probe isn't enough
+        assert!(!tracker.record(false, now));
+        assert!(tracker.state.online);
+        assert!(tracker.record(false, now));
+        assert!(!tracker.state.online);
+        assert_eq!(tracker.state.since, Some(now));
+        assert!(!tracker.record(false, now));
+
+        // The first good probe brings it back
+        assert!(tracker.record(true, now));
+        assert!(tracker.state.online);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod connectivity;
 pub mod stats;
 
+pub use connectivity::{Connectivity, ConnectivityMonitor};
 pub use stats::SystemStats;
@@ -12,12 +12,30 @@ use ratatui_image::protocol::StatefulProtocol;
 use crate::app::{AppState, Page};
 
 use super::widgets::{
-    activity_feed::render_activity_feed, clock::render_clock,
-    github_overview::render_github_overview, help_overlay::render_help_overlay, log_viewer,
-    news_feed::render_news_feed, spotify_player::render_spotify_player,
-    status_bar::render_status_bar, system_stats::render_system_stats,
+    activity_feed::render_activity_feed,
+    album_art::{has_album_art, render_album_art},
+    clock::render_clock,
+    github_overview::{render_github_overview, OVERVIEW_WITH_HEATMAP_HEIGHT},
+    help_overlay::render_help_overlay,
+    inbox::render_inbox,
+    language_breakdown::render_language_breakdown,
+    log_viewer,
+    news_feed::render_news_feed,
+    notifications::render_notifications,
+    received_feed::render_received_feed,
+    releases::render_releases,
+    repo_detail::render_repo_detail,
+    repo_table::render_repo_table,
+    spotify_player::render_spotify_player,
+    status_bar::render_status_bar,
+    system_stats::render_system_stats,
+    toast::render_toasts,
+    workflow_runs::render_workflow_runs,
 };
 
+/// Tallest the dashboard's releases box grows before it cuts off
+const MAX_RELEASES_HEIGHT: u16 = 8;
+
 /// Main render function for the application
 pub fn render_app(
     frame: &mut Frame,
@@ -46,6 +64,9 @@ pub fn render_app(
     // Render status bar
     render_status_bar(frame, main_chunks[2], state);
 
+    // Render toasts above the page content
+    render_toasts(frame, size, state);
+
     // Render help overlay if active
     if state.ui.show_help_overlay {
         render_help_overlay(frame, size);
@@ -61,27 +82,47 @@ pub fn render_app(
 }
 
 /// Render the header with navigation tabs
+/// Padding ratatui's `Tabs` puts on each side of a title
+const TAB_PADDING: u16 = 1;
+/// Divider between header tabs
+const TAB_DIVIDER: &str = " | ";
+
+/// Header tab text for a page, e.g. "1:Dashboard"
+fn tab_title(index: usize, page: Page) -> String {
+    format!("{}:{}", index + 1, page.tab_label())
+}
+
+/// Which header tab (if any) is at terminal column `col`.
+/// Mirrors the layout `Tabs` uses: border, then padded titles separated by the divider.
+pub fn tab_at_column(col: u16) -> Option<usize> {
+    let mut x = 1; // left border
+    for (i, page) in Page::ALL.iter().enumerate() {
+        let width = tab_title(i, *page).len() as u16 + TAB_PADDING * 2;
+        // Clicks on the divider count towards the tab before it
+        let end = x + width + TAB_DIVIDER.len() as u16;
+        if col >= x && col < end {
+            return Some(i);
+        }
+        x = end;
+    }
+    None
+}
+
 fn render_header(frame: &mut Frame, area: Rect, state: &AppState) {
-    let titles: Vec<Line> = vec![
-        "1:Dashboard",
-        "2:Repos",
-        "3:Activity",
-        "4:Spotify",
-        "5:Settings",
-    ]
-    .iter()
-    .enumerate()
-    .map(|(i, t)| {
-        let style = if i == state.ui.current_page.index() {
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-        Line::from(Span::styled(*t, style))
-    })
-    .collect();
+    let titles: Vec<Line> = Page::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let style = if i == state.ui.current_page.index() {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            Line::from(Span::styled(tab_title(i, *page), style))
+        })
+        .collect();
 
     let tabs = Tabs::new(titles)
         .block(
@@ -102,7 +143,7 @@ fn render_header(frame: &mut Frame, area: Rect, state: &AppState) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         )
-        .divider(Span::raw(" | "));
+        .divider(Span::raw(TAB_DIVIDER));
 
     frame.render_widget(tabs, area);
 }
@@ -121,6 +162,7 @@ fn render_page_content(
         Page::Activity => render_activity_page(frame, area, state, effects),
         Page::Spotify => render_spotify_page(frame, area, state, album_art_proto),
         Page::Settings => render_settings_page(frame, area, state),
+        Page::Inbox => render_inbox(frame, area, state),
     }
 }
 
@@ -138,14 +180,28 @@ fn render_dashboard(
         .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
         .split(area);
 
-    // Left column: top row (overview + activity), spotify player, log viewer
+    // Left column: top row (overview + activity), spotify player, bottom row
+    // (Actions board + log viewer)
+    // The top row grows to fit the contribution calendar when the screen is
+    // tall enough to keep the player usable
+    let show_heatmap = state.github.contributions.is_some()
+        && state.ui.selected_account.is_none()
+        && columns[0].height >= OVERVIEW_WITH_HEATMAP_HEIGHT * 2;
     let left_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(30), // GitHub overview + Activity
-            Constraint::Percentage(50), // Spotify player
-            Constraint::Percentage(20), // Log viewer
-        ])
+        .constraints(if show_heatmap {
+            [
+                Constraint::Length(OVERVIEW_WITH_HEATMAP_HEIGHT),
+                Constraint::Min(8),
+                Constraint::Percentage(25),
+            ]
+        } else {
+            [
+                Constraint::Percentage(30), // GitHub overview + Activity
+                Constraint::Percentage(45), // Spotify player
+                Constraint::Percentage(25), // Actions + Log viewer
+            ]
+        })
         .split(columns[0]);
 
     // Split top row into GitHub Overview (left) and Activity Feed (right)
@@ -157,32 +213,65 @@ fn render_dashboard(
     render_github_overview(frame, top_row[0], state);
     render_activity_feed(frame, top_row[1], state, effects);
     render_spotify_player(frame, left_chunks[1], state, album_art_proto);
-    log_viewer::render(frame, left_chunks[2], state);
 
-    // Right column: Clock, news, and system stats
+    let bottom_row = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(left_chunks[2]);
+    render_workflow_runs(frame, bottom_row[0], state);
+    log_viewer::render(frame, bottom_row[1], state);
+
+    // Right column: Clock, notifications (with a token), watched releases
+    // (when configured), news, and system stats
+    let notifications_height = if state.has_github_token { 10 } else { 0 };
+    let releases = state.github.watched_releases.len() as u16;
+    let releases_height = if releases == 0 {
+        0
+    } else {
+        (releases + 2).min(MAX_RELEASES_HEIGHT)
+    };
     let right_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(6),  // Clock
-            Constraint::Min(8),     // News feed
-            Constraint::Length(12), // System stats
+            Constraint::Length(6),                    // Clock
+            Constraint::Length(notifications_height), // Notifications
+            Constraint::Length(releases_height),      // Releases
+            Constraint::Min(8),                       // News feed
+            Constraint::Length(12),                   // System stats
         ])
         .split(columns[1]);
 
     render_clock(frame, right_chunks[0], state);
-    render_news_feed(frame, right_chunks[1], &state.news);
-    render_system_stats(frame, right_chunks[2], state);
+    if state.has_github_token {
+        render_notifications(frame, right_chunks[1], state);
+    }
+    if releases_height > 0 {
+        render_releases(frame, right_chunks[2], state);
+    }
+    render_news_feed(frame, right_chunks[3], state);
+    render_system_stats(frame, right_chunks[4], state);
 }
 
 /// Render the repositories page
 fn render_repositories_page(frame: &mut Frame, area: Rect, state: &AppState) {
-    use ratatui::widgets::{List, ListItem, Paragraph};
+    use ratatui::widgets::Paragraph;
+
+    if let Some(ref full_name) = state.ui.repo_detail {
+        render_repo_detail(frame, area, state, full_name);
+        return;
+    }
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
         .title(Span::styled(
-            " Repositories ",
+            match state.github.account(state.ui.selected_account) {
+                Some(account) => format!(" Repositories: {} ", account.login),
+                None if state.github.accounts.len() > 1 => {
+                    " Repositories: all accounts ".to_string()
+                }
+                None => " Repositories ".to_string(),
+            },
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -196,75 +285,16 @@ fn render_repositories_page(frame: &mut Frame, area: Rect, state: &AppState) {
         return;
     }
 
-    // Split into top starred and recently updated
+    // Repo table above the language breakdown
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Min(8), Constraint::Length(10)])
         .margin(1)
         .split(area);
 
-    // Draw outer block
     frame.render_widget(block, area);
-
-    // Top starred repos
-    let starred_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
-        .title(Span::styled(
-            " [*] Top Starred ",
-            Style::default().fg(Color::Yellow),
-        ));
-
-    let starred_repos = state.github.top_repos_by_stars(10);
-    let starred_items: Vec<ListItem> = starred_repos
-        .iter()
-        .map(|repo| {
-            let lang = repo.language.as_deref().unwrap_or("???");
-            let line = Line::from(vec![
-                Span::styled(
-                    format!("*{:<4}", repo.stargazers_count),
-                    Style::default().fg(Color::Yellow),
-                ),
-                Span::raw(" "),
-                Span::styled(&repo.name, Style::default().fg(Color::Cyan)),
-                Span::raw(" "),
-                Span::styled(format!("[{}]", lang), Style::default().fg(Color::DarkGray)),
-            ]);
-            ListItem::new(line)
-        })
-        .collect();
-
-    let starred_list = List::new(starred_items).block(starred_block);
-    frame.render_widget(starred_list, chunks[0]);
-
-    // Recently updated repos
-    let recent_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
-        .title(Span::styled(
-            " [>] Recently Updated ",
-            Style::default().fg(Color::Green),
-        ));
-
-    let recent_repos = state.github.recently_updated_repos(10);
-    let recent_items: Vec<ListItem> = recent_repos
-        .iter()
-        .map(|repo| {
-            let updated = repo
-                .pushed_at
-                .map(|t| crate::util::time::format_relative(t))
-                .unwrap_or_else(|| "???".to_string());
-            let line = Line::from(vec![
-                Span::styled(&repo.name, Style::default().fg(Color::Cyan)),
-                Span::raw(" "),
-                Span::styled(updated, Style::default().fg(Color::DarkGray)),
-            ]);
-            ListItem::new(line)
-        })
-        .collect();
-
-    let recent_list = List::new(recent_items).block(recent_block);
-    frame.render_widget(recent_list, chunks[1]);
+    render_repo_table(frame, chunks[0], state);
+    render_language_breakdown(frame, chunks[1], state);
 }
 
 /// Render the activity feed page
@@ -274,7 +304,17 @@ fn render_activity_page(
     state: &AppState,
     effects: &mut Vec<Effect>,
 ) {
-    render_activity_feed(frame, area, state, effects);
+    // Own activity beside the followed feed, when that is enabled
+    if !state.feed.enabled {
+        render_activity_feed(frame, area, state, effects);
+        return;
+    }
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    render_activity_feed(frame, columns[0], state, effects);
+    render_received_feed(frame, columns[1], state);
 }
 
 /// Render the full Spotify page (TUI Spotify-like experience)
@@ -286,7 +326,7 @@ fn render_spotify_page(
 ) {
     use ratatui::widgets::Paragraph;
 
-    let outer = Block::default()
+    let mut outer = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Green))
         .title(Span::styled(
@@ -295,6 +335,9 @@ fn render_spotify_page(
                 .fg(Color::Green)
                 .add_modifier(Modifier::BOLD),
         ));
+    if let Some(title) = super::widgets::spotify_player::sleep_title(&state.spotify) {
+        outer = outer.title(title);
+    }
 
     if !state.spotify.connected {
         let msg = if let Some(ref err) = state.spotify.error {
@@ -314,7 +357,7 @@ fn render_spotify_page(
     frame.render_widget(outer, area);
 
     if player.track_name.is_empty() {
-        let p = Paragraph::new("No track playing").style(Style::default().fg(Color::DarkGray));
+        let p = Paragraph::new(player.idle_message()).style(Style::default().fg(Color::DarkGray));
         frame.render_widget(p, inner);
         return;
     }
@@ -392,7 +435,7 @@ fn render_spotify_page(
     let np_inner = np_block.inner(right_rows[0]);
     frame.render_widget(np_block, right_rows[0]);
 
-    let has_art = album_art_proto.is_some();
+    let has_art = has_album_art(state, album_art_proto);
     let np_cols = if has_art && np_inner.width > 30 {
         let art_w = (np_inner.height * 2).min(np_inner.width / 2).max(8);
         Layout::default()
@@ -407,17 +450,8 @@ fn render_spotify_page(
     };
 
     // Album art
-    if let Some(proto) = album_art_proto.as_mut() {
-        let raw_art = np_cols[0];
-        if raw_art.width > 2 && raw_art.height > 1 {
-            let padded = Rect {
-                x: raw_art.x + 1,
-                y: raw_art.y,
-                width: raw_art.width.saturating_sub(2),
-                height: raw_art.height.saturating_sub(1),
-            };
-            frame.render_stateful_widget(ratatui_image::StatefulImage::default(), padded, proto);
-        }
+    if has_art {
+        render_album_art(frame, np_cols[0], state, album_art_proto);
     }
 
     // Track info
@@ -436,7 +470,13 @@ fn render_spotify_page(
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         )),
-        Line::from(""),
+        match player.episode_label() {
+            Some(label) => Line::from(Span::styled(
+                truncate_str_local(&label, max_w.saturating_sub(1)),
+                Style::default().fg(Color::Magenta),
+            )),
+            None => Line::from(""),
+        },
         Line::from(Span::styled(
             truncate_str_local(&player.album_name, max_w.saturating_sub(1)),
             Style::default()
@@ -461,7 +501,8 @@ fn render_spotify_page(
     let prog_area = right_rows[1];
     let prog_w = prog_area.width as usize;
     if prog_w >= 10 {
-        let time_left = crate::spotify::state::PlayerState::format_time(player.progress_ms);
+        let time_left =
+            crate::spotify::state::PlayerState::format_time(player.current_progress_ms());
         let time_right = crate::spotify::state::PlayerState::format_time(player.duration_ms);
         let time_text_len = time_left.len() + time_right.len() + 2;
         let bar_w = prog_w.saturating_sub(time_text_len + 2);
@@ -551,7 +592,7 @@ fn render_settings_page(frame: &mut Frame, area: Rect, state: &AppState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(12), // Keybinds
+            Constraint::Length(25), // Keybinds
             Constraint::Length(8),  // Animation settings
             Constraint::Length(6),  // Spotify settings
             Constraint::Min(5),     // Rate limit info
@@ -630,8 +671,23 @@ fn render_settings_page(frame: &mut Frame, area: Rect, state: &AppState) {
         Span::styled("NOT CONFIGURED", Style::default().fg(Color::DarkGray))
     };
 
+    let sleep_status = match &state.spotify.sleep_timer {
+        Some(timer) => Span::styled(
+            timer.label(chrono::Utc::now()),
+            Style::default().fg(Color::Magenta),
+        ),
+        None => Span::styled("OFF", Style::default().fg(Color::DarkGray)),
+    };
+
     let spotify_text = vec![
         Line::from(vec![Span::raw("Status: "), spotify_status]),
+        Line::from(vec![
+            Span::raw("Sleep timer: "),
+            sleep_status,
+            Span::raw("  (press "),
+            Span::styled("z", Style::default().fg(Color::Cyan)),
+            Span::raw(" to cycle)"),
+        ]),
         Line::from(vec![
             Span::raw("Press "),
             Span::styled("S", Style::default().fg(Color::Cyan)),
@@ -674,8 +730,61 @@ fn render_settings_page(frame: &mut Frame, area: Rect, state: &AppState) {
             Span::raw("Reset: "),
             Span::styled(reset_time, Style::default().fg(Color::DarkGray)),
         ]),
+        next_refresh_line(&state.github),
     ];
 
     let rate_info = Paragraph::new(rate_text).block(rate_block);
     frame.render_widget(rate_info, chunks[3]);
 }
+
+/// When the poller refreshes next, flagging backoff and server poll intervals
+fn next_refresh_line(github: &crate::github::GithubState) -> Line<'static> {
+    let Some(next) = github.next_refresh_at else {
+        return Line::from(vec![
+            Span::raw("Next refresh: "),
+            Span::styled("pending", Style::default().fg(Color::DarkGray)),
+        ]);
+    };
+    let secs = (next - chrono::Utc::now()).num_seconds().max(0);
+    let when = format!(
+        "{} (in {}m {:02}s)",
+        next.with_timezone(&chrono::Local).format("%H:%M:%S"),
+        secs / 60,
+        secs % 60
+    );
+
+    let hints = &github.poll_hints;
+    let (note, color) = if hints.throttled {
+        (" backing off".to_string(), Color::Red)
+    } else if let Some(interval) = hints.poll_interval_secs {
+        (
+            format!(" server poll interval {}s", interval),
+            Color::DarkGray,
+        )
+    } else {
+        (String::new(), Color::DarkGray)
+    };
+
+    Line::from(vec![
+        Span::raw("Next refresh: "),
+        Span::styled(when, Style::default().fg(Color::Cyan)),
+        Span::styled(note, Style::default().fg(color)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_at_column() {
+        // "│ 1:Dashboard | 2:Repos | ..."
+        assert_eq!(tab_at_column(0), None);
+        assert_eq!(tab_at_column(2), Some(0));
+        assert_eq!(tab_at_column(13), Some(0));
+        assert_eq!(tab_at_column(17), Some(1));
+        let last = Page::ALL.len() - 1;
+        assert_eq!(tab_at_column(200), None);
+        assert!((1..120).any(|col| tab_at_column(col) == Some(last)));
+    }
+}
@@ -8,6 +8,9 @@ use ratatui::{
 use tachyonfx::Effect;
 
 use crate::app::AppState;
+use crate::github::GithubEvent;
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::truncate_str;
 use crate::util::time::format_relative;
 
 /// Render the activity feed widget
@@ -21,8 +24,10 @@ pub fn render_activity_feed(frame: &mut Frame, area: Rect, state: &AppState, _ef
                 .fg(Color::Magenta)
                 .add_modifier(Modifier::BOLD),
         ));
+    let block = mark_offline(block, state, state.github.last_updated);
 
-    if state.github.events.is_empty() {
+    let events = state.github.feed_events();
+    if events.is_empty() {
         let empty_text = if state.github.status.is_fetching() {
             "Loading activity..."
         } else {
@@ -36,47 +41,64 @@ pub fn render_activity_feed(frame: &mut Frame, area: Rect, state: &AppState, _ef
         return;
     }
 
-    let items: Vec<ListItem> = state
-        .github
-        .events
-        .iter()
+    let items: Vec<ListItem> = events
+        .into_iter()
         .take(20)
-        .map(|event| {
-            let icon = event.event_type.icon();
-            let desc = event.event_type.description();
-            let time = format_relative(event.created_at);
+        .map(|event| ListItem::new(event_lines(event, area.width as usize)))
+        .collect();
 
-            // Extract repo name without username prefix
-            let repo_short = event
-                .repo_name
-                .split('/')
-                .last()
-                .unwrap_or(&event.repo_name);
+    let list = List::new(items).block(block);
+    frame.render_widget(list, area);
+}
 
-            let style = if event.is_new {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
+/// Lines for one event: icon, actor, description, repo and age, then any
+/// commit messages or issue/PR title indented underneath
+pub fn event_lines(event: &GithubEvent, width: usize) -> Vec<Line<'_>> {
+    let icon = event.event_type.icon();
+    let desc = event.description();
+    let time = format_relative(event.created_at);
 
-            let line = Line::from(vec![
-                Span::styled(format!("{} ", icon), style),
-                Span::styled(desc, Style::default().fg(Color::White)),
-                Span::raw(" "),
-                Span::styled(repo_short, Style::default().fg(Color::Cyan)),
-                Span::raw(" "),
-                Span::styled(time, Style::default().fg(Color::DarkGray)),
-                if event.is_new {
-                    Span::styled(" NEW", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
-                } else {
-                    Span::raw("")
-                },
-            ]);
+    // Extract repo name without username prefix
+    let repo_short = event
+        .repo_name
+        .split('/')
+        .last()
+        .unwrap_or(&event.repo_name);
 
-            ListItem::new(line)
-        })
-        .collect();
+    let style = if event.is_new {
+        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
 
-    let list = List::new(items).block(block);
-    frame.render_widget(list, area);
+    let mut spans = vec![Span::styled(format!("{} ", icon), style)];
+    // Stargazer/follower entries and others' activity name who did it
+    if let Some(ref actor) = event.actor {
+        spans.push(Span::styled(actor.as_str(), Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled(desc, Style::default().fg(Color::White)));
+    if !repo_short.is_empty() {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(repo_short, Style::default().fg(Color::Cyan)));
+    }
+    spans.extend([
+        Span::raw(" "),
+        Span::styled(time, Style::default().fg(Color::DarkGray)),
+        if event.is_new {
+            Span::styled(" NEW", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        } else {
+            Span::raw("")
+        },
+    ]);
+
+    let mut lines = vec![Line::from(spans)];
+    let detail_width = width.saturating_sub(8);
+    lines.extend(event.detail_lines().into_iter().map(|detail| {
+        Line::from(Span::styled(
+            format!("    {}", truncate_str(detail, detail_width)),
+            Style::default().fg(Color::DarkGray),
+        ))
+    }));
+    lines
 }
@@ -0,0 +1,307 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget, Frame};
+use ratatui_image::protocol::StatefulProtocol;
+
+use crate::app::AppState;
+use crate::spotify::state::AlbumArt;
+
+/// Characters used by the ASCII renderer, darkest to brightest
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
+
+/// Upper half block: foreground paints the top pixel, background the bottom one
+const HALF_BLOCK: &str = "\u{2580}";
+
+/// Maximum pixels sampled per axis when averaging a source region
+const MAX_SAMPLES: u32 = 4;
+
+/// How album art is drawn when the terminal has no graphics protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtMode {
+    /// Half-block cells with 24-bit colours
+    TrueColor,
+    /// Half-block cells mapped to the xterm 256-colour palette
+    Ansi256,
+    /// Uncoloured brightness ramp (Linux console, dumb terminals)
+    Ascii,
+}
+
+impl ArtMode {
+    /// Parse a mode name as used in `CONTROL_SYSTEM_ART_MODE`
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            "256" | "256color" | "ansi256" => Some(Self::Ansi256),
+            "ascii" => Some(Self::Ascii),
+            _ => None,
+        }
+    }
+
+    /// Pick a mode from the environment.
+    /// `CONTROL_SYSTEM_ART_MODE` wins, then `COLORTERM`, then `TERM`.
+    pub fn detect() -> Self {
+        if let Some(mode) = std::env::var("CONTROL_SYSTEM_ART_MODE")
+            .ok()
+            .and_then(|v| Self::parse(&v))
+        {
+            return mode;
+        }
+
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        Self::from_terminal(&colorterm, &term)
+    }
+
+    fn from_terminal(colorterm: &str, term: &str) -> Self {
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else {
+            // The Linux console (TERM=linux) and unknown terminals
+            Self::Ascii
+        }
+    }
+}
+
+/// Whether there is any album art to draw (protocol image or fallback)
+pub fn has_album_art(state: &AppState, album_art_proto: &Option<StatefulProtocol>) -> bool {
+    album_art_proto.is_some()
+        || (state.ui.art_fallback.is_some() && state.spotify.player.album_art.is_some())
+}
+
+/// Render album art into `area` with 1-cell padding, using the terminal's
+/// image protocol when available and the built-in cell renderer otherwise.
+pub fn render_album_art(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    album_art_proto: &mut Option<StatefulProtocol>,
+) {
+    if area.width <= 2 || area.height <= 1 {
+        return;
+    }
+    let padded = Rect {
+        x: area.x + 1,
+        y: area.y,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(1),
+    };
+
+    if let Some(proto) = album_art_proto.as_mut() {
+        frame.render_stateful_widget(ratatui_image::StatefulImage::default(), padded, proto);
+    } else if let (Some(mode), Some(art)) = (state.ui.art_fallback, &state.spotify.player.album_art)
+    {
+        frame.render_widget(CellArt::new(art, mode), padded);
+    }
+}
+
+/// Widget drawing RGBA album art with terminal cells
+pub struct CellArt<'a> {
+    art: &'a AlbumArt,
+    mode: ArtMode,
+}
+
+impl<'a> CellArt<'a> {
+    pub fn new(art: &'a AlbumArt, mode: ArtMode) -> Self {
+        Self { art, mode }
+    }
+}
+
+impl Widget for CellArt<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 || self.art.width == 0 || self.art.height == 0 {
+            return;
+        }
+
+        match self.mode {
+            ArtMode::TrueColor | ArtMode::Ansi256 => {
+                // Each cell holds two vertically stacked pixels, so a cell is square
+                let size = area.width.min(area.height * 2);
+                let x0 = area.x + (area.width - size) / 2;
+                let rows = size.div_ceil(2);
+                for row in 0..rows {
+                    for col in 0..size {
+                        let top = self.sample(col, row * 2, size, size);
+                        let bottom = self.sample(col, row * 2 + 1, size, size);
+                        if let Some(cell) = buf.cell_mut((x0 + col, area.y + row)) {
+                            cell.set_symbol(HALF_BLOCK)
+                                .set_fg(self.color(top))
+                                .set_bg(self.color(bottom));
+                        }
+                    }
+                }
+            }
+            ArtMode::Ascii => {
+                // Cells are roughly twice as tall as wide
+                let rows = area.height.min(area.width / 2).max(1);
+                let cols = (rows * 2).min(area.width);
+                let x0 = area.x + (area.width - cols) / 2;
+                for row in 0..rows {
+                    for col in 0..cols {
+                        let rgb = self.sample(col, row, cols, rows);
+                        if let Some(cell) = buf.cell_mut((x0 + col, area.y + row)) {
+                            cell.set_char(ascii_for(rgb));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl CellArt<'_> {
+    /// Average colour of the source region covered by grid cell (gx, gy)
+    /// in a `grid_w` x `grid_h` grid, composited over black.
+    fn sample(&self, gx: u16, gy: u16, grid_w: u16, grid_h: u16) -> [u8; 3] {
+        let (w, h) = (self.art.width, self.art.height);
+        let sx0 = gx as u32 * w / grid_w as u32;
+        let sx1 = ((gx as u32 + 1) * w / grid_w as u32).clamp(sx0 + 1, w);
+        let sy0 = gy as u32 * h / grid_h as u32;
+        let sy1 = ((gy as u32 + 1) * h / grid_h as u32).clamp(sy0 + 1, h);
+        if sx0 >= w || sy0 >= h {
+            return [0, 0, 0];
+        }
+
+        let step_x = ((sx1 - sx0) / MAX_SAMPLES).max(1);
+        let step_y = ((sy1 - sy0) / MAX_SAMPLES).max(1);
+        let mut sum = [0u32; 3];
+        let mut count = 0u32;
+        for y in (sy0..sy1).step_by(step_y as usize) {
+            for x in (sx0..sx1).step_by(step_x as usize) {
+                let i = ((y * w + x) * 4) as usize;
+                if let Some(px) = self.art.rgba_data.get(i..i + 4) {
+                    let alpha = px[3] as u32;
+                    for (s, &p) in sum.iter_mut().zip(px) {
+                        *s += p as u32 * alpha / 255;
+                    }
+                    count += 1;
+                }
+            }
+        }
+        if count == 0 {
+            return [0, 0, 0];
+        }
+        [
+            (sum[0] / count) as u8,
+            (sum[1] / count) as u8,
+            (sum[2] / count) as u8,
+        ]
+    }
+
+    fn color(&self, [r, g, b]: [u8; 3]) -> Color {
+        match self.mode {
+            ArtMode::Ansi256 => Color::Indexed(rgb_to_ansi256(r, g, b)),
+            _ => Color::Rgb(r, g, b),
+        }
+    }
+}
+
+/// Pick the ramp character for a pixel's perceived brightness
+fn ascii_for([r, g, b]: [u8; 3]) -> char {
+    let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+    let idx = luma as usize * (ASCII_RAMP.len() - 1) / 255;
+    ASCII_RAMP[idx] as char
+}
+
+/// Map an RGB colour to the nearest entry of the xterm 256-colour palette
+/// (6x6x6 cube or 24-step grayscale ramp).
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    fn cube_index(v: u8) -> u8 {
+        if v < 48 {
+            0
+        } else if v < 115 {
+            1
+        } else {
+            (v - 35) / 40
+        }
+    }
+    fn cube_value(i: u8) -> u8 {
+        if i == 0 {
+            0
+        } else {
+            55 + i * 40
+        }
+    }
+    fn dist(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+        let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+        d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+    }
+
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = (cube_value(ri), cube_value(gi), cube_value(bi));
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_i = if avg > 238 {
+        23
+    } else {
+        (avg.saturating_sub(3) / 10) as u8
+    };
+    let gray_v = 8 + gray_i * 10;
+
+    if dist((gray_v, gray_v, gray_v), (r, g, b)) < dist(cube, (r, g, b)) {
+        232 + gray_i
+    } else {
+        16 + 36 * ri + 6 * gi + bi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_art(w: u32, h: u32, rgba: [u8; 4]) -> AlbumArt {
+        AlbumArt {
+            width: w,
+            height: h,
+            rgba_data: rgba.repeat((w * h) as usize),
+            source_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_rgb_to_ansi256() {
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+    }
+
+    #[test]
+    fn test_detect_from_terminal() {
+        assert_eq!(
+            ArtMode::from_terminal("truecolor", "xterm"),
+            ArtMode::TrueColor
+        );
+        assert_eq!(
+            ArtMode::from_terminal("", "xterm-256color"),
+            ArtMode::Ansi256
+        );
+        assert_eq!(ArtMode::from_terminal("", "linux"), ArtMode::Ascii);
+        assert_eq!(ArtMode::parse("256"), Some(ArtMode::Ansi256));
+    }
+
+    #[test]
+    fn test_half_block_render() {
+        let art = solid_art(8, 8, [200, 10, 10, 255]);
+        let area = Rect::new(0, 0, 4, 2);
+        let mut buf = Buffer::empty(area);
+        CellArt::new(&art, ArtMode::TrueColor).render(area, &mut buf);
+
+        let cell = &buf[(0, 0)];
+        assert_eq!(cell.symbol(), HALF_BLOCK);
+        assert_eq!(cell.fg, Color::Rgb(200, 10, 10));
+        assert_eq!(cell.bg, Color::Rgb(200, 10, 10));
+    }
+
+    #[test]
+    fn test_ascii_render() {
+        let area = Rect::new(0, 0, 4, 2);
+
+        let mut buf = Buffer::empty(area);
+        CellArt::new(&solid_art(4, 4, [255, 255, 255, 255]), ArtMode::Ascii).render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].symbol(), "@");
+
+        let mut buf = Buffer::empty(area);
+        CellArt::new(&solid_art(4, 4, [255, 255, 255, 0]), ArtMode::Ascii).render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].symbol(), " ");
+    }
+}
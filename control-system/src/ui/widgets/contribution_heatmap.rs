@@ -0,0 +1,139 @@
+use chrono::{Datelike, Local};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::github::{ContributionCalendar, ContributionDay};
+
+/// Cell glyph for a day
+const CELL: &str = "■";
+
+/// Width of the weekday label column
+const LABEL_WIDTH: u16 = 4;
+
+/// Height of the heatmap section (title rule, months, 7 days, summary)
+pub const HEATMAP_HEIGHT: u16 = 10;
+
+/// Colour for a contribution level (0-4), using the xterm palette greens
+fn level_color(level: u8) -> Color {
+    match level {
+        0 => Color::Indexed(237),
+        1 => Color::Indexed(22),
+        2 => Color::Indexed(28),
+        3 => Color::Indexed(34),
+        _ => Color::Indexed(46),
+    }
+}
+
+/// Group days into Sunday-first weeks, like the github.com calendar
+fn weeks(days: &[ContributionDay]) -> Vec<[Option<&ContributionDay>; 7]> {
+    let mut weeks: Vec<[Option<&ContributionDay>; 7]> = Vec::new();
+    for day in days {
+        let row = day.date.weekday().num_days_from_sunday() as usize;
+        if weeks.is_empty() || row == 0 {
+            weeks.push([None; 7]);
+        }
+        if let Some(week) = weeks.last_mut() {
+            week[row] = Some(day);
+        }
+    }
+    weeks
+}
+
+/// Render the contribution calendar heatmap with streak counts as a
+/// section of the GitHub overview, under a titled rule
+pub fn render_contribution_heatmap(frame: &mut Frame, area: Rect, calendar: &ContributionCalendar) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(
+            " Contributions ",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(heatmap_lines(calendar, inner.width)), inner);
+}
+
+/// Build the month header, the seven weekday rows and the summary line,
+/// showing as many of the most recent weeks as fit in `width`
+fn heatmap_lines(calendar: &ContributionCalendar, width: u16) -> Vec<Line<'static>> {
+    let all_weeks = weeks(&calendar.days);
+    let visible = (width.saturating_sub(LABEL_WIDTH) as usize).min(all_weeks.len());
+    let shown = &all_weeks[all_weeks.len() - visible..];
+
+    // Month labels above the first week of each month
+    let mut header = vec![' '; visible];
+    let mut next_free = 0;
+    let mut prev_month = None;
+    for (i, week) in shown.iter().enumerate() {
+        let Some(month) = week.iter().flatten().next().map(|d| d.date.month()) else {
+            continue;
+        };
+        if prev_month.is_some_and(|m| m != month) && i >= next_free && i + 3 <= visible {
+            let name = chrono::Month::try_from(month as u8)
+                .map(|m| m.name())
+                .unwrap_or("");
+            for (j, c) in name.chars().take(3).enumerate() {
+                header[i + j] = c;
+            }
+            next_free = i + 4;
+        }
+        prev_month = Some(month);
+    }
+    let header = format!(
+        "{}{}",
+        " ".repeat(LABEL_WIDTH as usize),
+        header.into_iter().collect::<String>()
+    );
+
+    let mut lines = vec![Line::from(Span::styled(
+        header,
+        Style::default().fg(Color::DarkGray),
+    ))];
+
+    for row in 0..7 {
+        let label = match row {
+            1 => "Mon ",
+            3 => "Wed ",
+            5 => "Fri ",
+            _ => "    ",
+        };
+        let mut spans = vec![Span::styled(label, Style::default().fg(Color::DarkGray))];
+        spans.extend(shown.iter().map(|week| match week[row] {
+            Some(day) => Span::styled(CELL, Style::default().fg(level_color(day.level))),
+            None => Span::raw(" "),
+        }));
+        lines.push(Line::from(spans));
+    }
+
+    let today = Local::now().date_naive();
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("{}", calendar.total),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" in the last year  "),
+        Span::styled("streak ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{}d", calendar.current_streak(today)),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled("  longest ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{}d", calendar.longest_streak()),
+            Style::default().fg(Color::Yellow),
+        ),
+    ]));
+
+    lines
+}
@@ -7,7 +7,19 @@ use ratatui::{
 };
 
 use crate::app::AppState;
-use crate::util::format::format_count;
+use crate::github::history::History;
+use crate::ui::widgets::contribution_heatmap::{render_contribution_heatmap, HEATMAP_HEIGHT};
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::{format_count, format_delta, sparkline};
+
+/// Days of star history shown in the sparkline
+const TREND_DAYS: usize = 14;
+
+/// Lines of profile and stats text
+const STATS_HEIGHT: u16 = 8;
+
+/// Height the overview needs to show the contribution calendar too
+pub const OVERVIEW_WITH_HEATMAP_HEIGHT: u16 = STATS_HEIGHT + HEATMAP_HEIGHT + 2;
 
 const CAT_1: &str = r#"
     /\_/\
@@ -53,21 +65,77 @@ fn get_ascii_cat() -> &'static str {
     }
 }
 
+/// Start of the local day, for "today" deltas
+fn start_of_today() -> chrono::DateTime<chrono::Utc> {
+    let local = chrono::Local::now();
+    local
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .unwrap_or_else(|| local.with_timezone(&chrono::Utc))
+}
+
+/// Star sparkline with today's and this week's gains, once there is history
+fn trend_line(history: &History) -> Line<'static> {
+    if history.samples.len() < 2 {
+        return Line::from("");
+    }
+    let now = chrono::Utc::now();
+    let series = history.daily_series(now, TREND_DAYS, |s| s.stars);
+    let today = history.delta(start_of_today(), |s| s.stars);
+    let week = history.delta(now - chrono::Duration::days(7), |s| s.stars);
+    let delta_color = |d: i64| if d > 0 { Color::Green } else { Color::DarkGray };
+
+    Line::from(vec![
+        Span::styled("[~] ", Style::default()),
+        Span::styled(sparkline(&series), Style::default().fg(Color::Yellow)),
+        Span::raw(" "),
+        Span::styled(format_delta(today), Style::default().fg(delta_color(today))),
+        Span::raw(" stars today, "),
+        Span::styled(format_delta(week), Style::default().fg(delta_color(week))),
+        Span::raw(" this week"),
+    ])
+}
+
 /// Render the GitHub overview widget
 pub fn render_github_overview(frame: &mut Frame, area: Rect, state: &AppState) {
+    // A selected account replaces the primary profile and combined stats
+    let selected = state.github.account(state.ui.selected_account);
+    let title = match selected {
+        Some(account) => format!(" GitHub Overview: {} ", account.login),
+        None => " GitHub Overview ".to_string(),
+    };
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
         .title(Span::styled(
-            " GitHub Overview ",
+            title,
             Style::default()
                 .fg(Color::Green)
                 .add_modifier(Modifier::BOLD),
         ));
+    let block = mark_offline(block, state, state.github.last_updated);
 
-    let inner = block.inner(area);
+    let mut inner = block.inner(area);
     frame.render_widget(block, area);
 
+    // Contribution calendar (the primary user's) below the stats, when
+    // there is room for both
+    if let (None, Some(calendar)) = (selected, state.github.contributions.as_ref()) {
+        if inner.height >= STATS_HEIGHT + HEATMAP_HEIGHT {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(STATS_HEIGHT),
+                    Constraint::Length(HEATMAP_HEIGHT),
+                ])
+                .split(inner);
+            render_contribution_heatmap(frame, rows[1], calendar);
+            inner = rows[0];
+        }
+    }
+
     // Split inner into left (stats) and right (ASCII cat)
     let cols = Layout::default()
         .direction(Direction::Horizontal)
@@ -75,8 +143,10 @@ pub fn render_github_overview(frame: &mut Frame, area: Rect, state: &AppState) {
         .split(inner);
 
     // Left side: GitHub stats
-    if let Some(ref profile) = state.github.profile {
-        let stats = &state.github.stats;
+    let profile = selected.map_or(state.github.profile.as_ref(), |a| a.profile.as_ref());
+    if let Some(profile) = profile {
+        let stats = selected.map_or(&state.github.stats, |a| &a.stats);
+        let is_org = selected.is_some_and(|a| a.kind == crate::github::AccountKind::Organization);
 
         let name_line = Line::from(vec![
             Span::styled(
@@ -89,6 +159,10 @@ pub fn render_github_overview(frame: &mut Frame, area: Rect, state: &AppState) {
                 format!(" (@{})", profile.login),
                 Style::default().fg(Color::DarkGray),
             ),
+            Span::styled(
+                if is_org { " [org]" } else { "" },
+                Style::default().fg(Color::Magenta),
+            ),
         ]);
 
         let bio_line = if let Some(ref bio) = profile.bio {
@@ -100,7 +174,7 @@ pub fn render_github_overview(frame: &mut Frame, area: Rect, state: &AppState) {
             Line::from("")
         };
 
-        let followers_line = Line::from(vec![
+        let mut followers_line = Line::from(vec![
             Span::styled("[F] ", Style::default()),
             Span::styled(
                 format_count(profile.followers as u64),
@@ -113,6 +187,27 @@ pub fn render_github_overview(frame: &mut Frame, area: Rect, state: &AppState) {
             ),
             Span::raw(" following"),
         ]);
+        // History tracks the primary user and combined totals only
+        let followers_week = if selected.is_none() {
+            state
+                .github
+                .history
+                .delta(chrono::Utc::now() - chrono::Duration::days(7), |s| {
+                    s.followers
+                })
+        } else {
+            0
+        };
+        if followers_week != 0 {
+            followers_line.spans.push(Span::styled(
+                format!(" ({} wk)", format_delta(followers_week)),
+                Style::default().fg(if followers_week > 0 {
+                    Color::Green
+                } else {
+                    Color::Red
+                }),
+            ));
+        }
 
         let stats_line = Line::from(vec![
             Span::styled("[*] ", Style::default().fg(Color::Yellow)),
@@ -153,7 +248,11 @@ pub fn render_github_overview(frame: &mut Frame, area: Rect, state: &AppState) {
             Line::from(""),
             followers_line,
             stats_line,
-            Line::from(""),
+            if selected.is_none() {
+                trend_line(&state.github.history)
+            } else {
+                Line::from("")
+            },
             status_line,
         ];
 
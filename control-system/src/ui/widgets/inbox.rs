@@ -0,0 +1,141 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::github::{CiStatus, InboxItem, InboxKind, ReviewState};
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::truncate_str;
+use crate::util::time::format_age;
+
+/// Section order on the page
+const SECTIONS: [InboxKind; 3] = [
+    InboxKind::ReviewRequested,
+    InboxKind::AuthoredPr,
+    InboxKind::AssignedIssue,
+];
+
+/// Render the pull request / issue inbox page
+pub fn render_inbox(frame: &mut Frame, area: Rect, state: &AppState) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            " Inbox ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let block = mark_offline(block, state, state.github.last_updated);
+
+    if state.github.inbox.is_empty() {
+        let msg = if !state.has_github_token {
+            "Set GITHUB_TOKEN to load your pull requests and issues"
+        } else if state.github.status.is_fetching() {
+            "Loading pull requests and issues..."
+        } else {
+            "Nothing needs your attention"
+        };
+        let empty = Paragraph::new(msg)
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let width = inner.width as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    for kind in SECTIONS {
+        let items = state.github.inbox_items(kind);
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(
+                kind.title(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" ({})", items.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        if items.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  none",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.extend(items.into_iter().map(|item| inbox_line(item, width)));
+    }
+
+    // Keep the last line reachable but don't scroll past it
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    let scroll = state.ui.scroll_offset.min(max_scroll) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+}
+
+/// One row: CI badge, repo#number, title, labels, review state, age
+fn inbox_line(item: &InboxItem, width: usize) -> Line<'static> {
+    let (ci_text, ci_color) = match item.ci {
+        Some(CiStatus::Success) => ("[ok]", Color::Green),
+        Some(CiStatus::Failure) => ("[x] ", Color::Red),
+        Some(CiStatus::Pending) => ("[..]", Color::Yellow),
+        None => ("[--]", Color::DarkGray),
+    };
+    let review = match item.review {
+        Some(ReviewState::Approved) => Some(("approved", Color::Green)),
+        Some(ReviewState::ChangesRequested) => Some(("changes", Color::Red)),
+        Some(ReviewState::ReviewRequired) => Some(("review", Color::Yellow)),
+        None => None,
+    };
+
+    let reference = format!("{}#{}", item.repo_name, item.number);
+    let labels = if item.labels.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", item.labels.join(","))
+    };
+    let age = format_age(item.updated_at);
+    let draft = if item.is_draft { " draft" } else { "" };
+
+    // Title gets whatever space is left after the fixed columns
+    let fixed = 2
+        + ci_text.len()
+        + 1
+        + reference.len()
+        + 1
+        + labels.len()
+        + draft.len()
+        + review.map_or(0, |(r, _)| r.len() + 1)
+        + 1
+        + age.len();
+    let title = truncate_str(&item.title, width.saturating_sub(fixed).max(10));
+
+    let mut spans = vec![
+        Span::raw("  "),
+        Span::styled(ci_text, Style::default().fg(ci_color)),
+        Span::raw(" "),
+        Span::styled(reference, Style::default().fg(Color::Cyan)),
+        Span::raw(" "),
+        Span::styled(title, Style::default().fg(Color::White)),
+        Span::styled(labels, Style::default().fg(Color::Magenta)),
+        Span::styled(draft, Style::default().fg(Color::DarkGray)),
+    ];
+    if let Some((text, color)) = review {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(text, Style::default().fg(color)));
+    }
+    spans.push(Span::raw(" "));
+    spans.push(Span::styled(age, Style::default().fg(Color::DarkGray)));
+    Line::from(spans)
+}
@@ -0,0 +1,102 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::widgets::repo_spotlight::language_color;
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::truncate_str;
+
+/// Languages listed by name; the rest are folded into "Other"
+const MAX_LANGUAGES: usize = 6;
+
+/// Render languages across non-fork repos as a stacked bar with one
+/// percentage bar per language underneath
+pub fn render_language_breakdown(frame: &mut Frame, area: Rect, state: &AppState) {
+    let breakdown = state.github.language_breakdown(state.ui.selected_account);
+    let title = if !breakdown.by_bytes {
+        " Languages (by repos) ".to_string()
+    } else if breakdown.repos_counted < breakdown.repos {
+        // Bytes are only fetched for the most recently pushed repos
+        format!(
+            " Languages (by bytes, {} of {} repos) ",
+            breakdown.repos_counted, breakdown.repos
+        )
+    } else {
+        " Languages (by bytes) ".to_string()
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(title, Style::default().fg(Color::Magenta)));
+    let block = mark_offline(block, state, state.github.last_updated);
+
+    let mut shares: Vec<(&str, f64, Color)> = breakdown
+        .shares()
+        .into_iter()
+        .map(|(name, pct)| (name, pct, language_color(name)))
+        .collect();
+    if shares.is_empty() {
+        let empty = Paragraph::new("No language data")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(empty, area);
+        return;
+    }
+    if shares.len() > MAX_LANGUAGES {
+        let other: f64 = shares[MAX_LANGUAGES - 1..]
+            .iter()
+            .map(|(_, pct, _)| pct)
+            .sum();
+        shares.truncate(MAX_LANGUAGES - 1);
+        shares.push(("Other", other, Color::DarkGray));
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let width = inner.width as usize;
+
+    let stacked = stacked_bar(shares.iter().map(|(_, pct, color)| (*pct, *color)), width);
+    let mut lines = vec![stacked, Line::from("")];
+    let name_width = 10;
+    let bar_width = width.saturating_sub(name_width + 8);
+    for (name, pct, color) in &shares {
+        let cells = ((pct / 100.0) * bar_width as f64).round() as usize;
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<w$}", truncate_str(name, name_width), w = name_width),
+                Style::default().fg(*color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("▇".repeat(cells.max(1)), Style::default().fg(*color)),
+            Span::styled(format!(" {:.1}%", pct), Style::default().fg(Color::White)),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// A `width`-cell bar split between `(percent, color)` segments, the last
+/// one taking whatever rounding left over
+pub fn stacked_bar(
+    segments: impl ExactSizeIterator<Item = (f64, Color)>,
+    width: usize,
+) -> Line<'static> {
+    let count = segments.len();
+    let mut spans = Vec::with_capacity(count);
+    let mut used = 0;
+    for (i, (pct, color)) in segments.enumerate() {
+        let cells = if i + 1 == count {
+            width.saturating_sub(used)
+        } else {
+            ((pct / 100.0) * width as f64).round() as usize
+        };
+        let cells = cells.min(width.saturating_sub(used));
+        used += cells;
+        spans.push(Span::styled("█".repeat(cells), Style::default().fg(color)));
+    }
+    Line::from(spans)
+}
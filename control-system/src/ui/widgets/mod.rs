@@ -1,10 +1,21 @@
 pub mod activity_feed;
+pub mod album_art;
 pub mod clock;
+pub mod contribution_heatmap;
 pub mod github_overview;
 pub mod help_overlay;
+pub mod inbox;
+pub mod language_breakdown;
 pub mod log_viewer;
 pub mod news_feed;
+pub mod notifications;
+pub mod received_feed;
+pub mod releases;
+pub mod repo_detail;
 pub mod repo_spotlight;
+pub mod repo_table;
 pub mod spotify_player;
 pub mod status_bar;
 pub mod system_stats;
+pub mod toast;
+pub mod workflow_runs;
@@ -6,11 +6,13 @@ use ratatui::{
     Frame,
 };
 
-use crate::news::NewsFeed;
+use crate::app::AppState;
+use crate::ui::widgets::status_bar::mark_offline;
 use crate::util::format::truncate_str;
 
 /// Render the news headlines widget
-pub fn render_news_feed(frame: &mut Frame, area: Rect, news: &NewsFeed) {
+pub fn render_news_feed(frame: &mut Frame, area: Rect, state: &AppState) {
+    let news = &state.news;
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
@@ -20,6 +22,7 @@ pub fn render_news_feed(frame: &mut Frame, area: Rect, news: &NewsFeed) {
                 .fg(Color::Red)
                 .add_modifier(Modifier::BOLD),
         ));
+    let block = mark_offline(block, state, state.news.last_updated);
 
     if news.is_loading && news.items.is_empty() {
         let loading = ratatui::widgets::Paragraph::new("Loading news...")
@@ -0,0 +1,103 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::github::NotificationReason;
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::truncate_str;
+use crate::util::time::format_age;
+
+/// Colour for a notification reason tag
+fn reason_color(reason: &NotificationReason) -> Color {
+    match reason {
+        NotificationReason::ReviewRequested => Color::Yellow,
+        NotificationReason::Mention => Color::Magenta,
+        NotificationReason::CiActivity => Color::Red,
+        NotificationReason::Assigned => Color::Cyan,
+        NotificationReason::Other(_) => Color::DarkGray,
+    }
+}
+
+/// Render unread notifications grouped by repository, then reason.
+/// The highlighted thread can be marked read with `m` (all with `M`).
+pub fn render_notifications(frame: &mut Frame, area: Rect, state: &AppState) {
+    let threads = state.github.notification_threads();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            format!(" Notifications ({}) ", threads.len()),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let block = mark_offline(block, state, state.github.last_updated);
+
+    if threads.is_empty() {
+        let empty = Paragraph::new("All caught up")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let width = inner.width as usize;
+    let selected = state.selected_notification_index();
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    let mut current_repo: Option<&str> = None;
+    let mut current_reason: Option<&NotificationReason> = None;
+
+    for (i, thread) in threads.iter().enumerate() {
+        if current_repo != Some(thread.repo_name.as_str()) {
+            current_repo = Some(thread.repo_name.as_str());
+            current_reason = None;
+            lines.push(Line::from(Span::styled(
+                truncate_str(&thread.repo_name, width),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        if current_reason != Some(&thread.reason) {
+            current_reason = Some(&thread.reason);
+            let count = threads[i..]
+                .iter()
+                .take_while(|t| t.repo_name == thread.repo_name && t.reason == thread.reason)
+                .count();
+            lines.push(Line::from(Span::styled(
+                truncate_str(&format!(" {} ({})", thread.reason.heading(), count), width),
+                Style::default().fg(reason_color(&thread.reason)),
+            )));
+        }
+
+        let age = format_age(thread.updated_at);
+        let title_width = width.saturating_sub(age.len() + 4);
+        let mut line = Line::from(vec![
+            Span::raw("   "),
+            Span::styled(
+                truncate_str(&thread.title, title_width),
+                Style::default().fg(Color::White),
+            ),
+            Span::raw(" "),
+            Span::styled(age, Style::default().fg(Color::DarkGray)),
+        ]);
+        if i == selected {
+            line = line.style(Style::default().add_modifier(Modifier::REVERSED));
+            selected_line = lines.len();
+        }
+        lines.push(line);
+    }
+
+    // Keep the highlighted thread in view
+    let scroll = selected_line.saturating_sub(inner.height.saturating_sub(1) as usize) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+}
@@ -0,0 +1,81 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::widgets::activity_feed::event_lines;
+use crate::ui::widgets::status_bar::mark_offline;
+
+/// Render what followed users and watched repos are doing, with the
+/// highlighted entry (scroll keys) as the target for muting
+pub fn render_received_feed(frame: &mut Frame, area: Rect, state: &AppState) {
+    let events = state.received_feed();
+    let muted = state.feed.muted_repos.len() + state.feed.muted_actors.len();
+
+    let mut title = " Following".to_string();
+    if let Some(kind) = state.ui.feed_kind {
+        title.push_str(&format!(": {}", kind.label()));
+    }
+    if muted > 0 {
+        title.push_str(&format!(" ({} muted)", muted));
+    }
+    title.push(' ');
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(
+            Line::from(Span::styled(
+                " f filter  x/X mute repo/user ",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+    let block = mark_offline(block, state, state.github.last_updated);
+
+    if events.is_empty() {
+        let msg = if state.github.status.is_fetching() {
+            "Loading activity..."
+        } else if state.github.received_events.is_empty() {
+            "No activity from people and repos you follow"
+        } else {
+            "Everything here is filtered out or muted"
+        };
+        let empty = Paragraph::new(msg)
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let selected = state.ui.scroll_offset.min(events.len() - 1);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    for (i, event) in events.into_iter().enumerate() {
+        let mut event_lines = event_lines(event, inner.width as usize);
+        if i == selected {
+            selected_line = lines.len();
+            event_lines[0] = event_lines[0]
+                .clone()
+                .style(Style::default().add_modifier(Modifier::REVERSED));
+        }
+        lines.extend(event_lines);
+    }
+
+    // Keep the highlighted event in view
+    let scroll = selected_line.saturating_sub(inner.height.saturating_sub(1) as usize) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+}
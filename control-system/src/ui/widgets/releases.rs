@@ -0,0 +1,84 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::truncate_str;
+use crate::util::time::format_age;
+
+/// Render the latest release of each watched repo. Releases that appeared
+/// since the last acknowledgement are highlighted until `n` is pressed.
+/// Only shown once there are releases (the dashboard gives it no room
+/// before then).
+pub fn render_releases(frame: &mut Frame, area: Rect, state: &AppState) {
+    let new_count = state.github.new_release_count();
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if new_count > 0 {
+            Color::Magenta
+        } else {
+            Color::Cyan
+        }))
+        .title(Span::styled(
+            if new_count > 0 {
+                format!(" Releases ({} new) ", new_count)
+            } else {
+                " Releases ".to_string()
+            },
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    block = mark_offline(block, state, state.github.last_updated);
+    if new_count > 0 {
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                " n acknowledge ",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    let width = block.inner(area).width as usize;
+    let lines: Vec<Line> = state
+        .github
+        .watched_releases
+        .iter()
+        .map(|watched| {
+            let tag_style = if watched.acknowledged {
+                Style::default().fg(Color::Magenta)
+            } else {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            };
+            let marker = if watched.acknowledged { "  " } else { "* " };
+            let age = watched
+                .release
+                .published_at
+                .map(format_age)
+                .unwrap_or_else(|| if watched.is_tag { "tag" } else { "" }.to_string());
+            let repo_width =
+                width.saturating_sub(marker.len() + watched.release.tag.len() + age.len() + 2);
+            Line::from(vec![
+                Span::styled(marker, tag_style),
+                Span::styled(
+                    truncate_str(&watched.repo, repo_width),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(" "),
+                Span::styled(watched.release.tag.as_str(), tag_style),
+                Span::raw(" "),
+                Span::styled(age, Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,309 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::github::{RepoDetail, RepoTraffic};
+use crate::ui::widgets::language_breakdown::stacked_bar;
+use crate::ui::widgets::repo_spotlight::language_color;
+use crate::util::format::{format_count, truncate_str};
+use crate::util::time::format_age;
+
+/// Render the detail page for one repo, opened from the Repositories page
+pub fn render_repo_detail(frame: &mut Frame, area: Rect, state: &AppState, full_name: &str) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            format!(" {} ", full_name),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(
+            Line::from(Span::styled(
+                " Esc back ",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+
+    let Some(detail) = state.github.repo_details.get(full_name) else {
+        let loading = Paragraph::new("Loading repository details...")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(loading, area);
+        return;
+    };
+    if let Some(ref error) = detail.error {
+        let failed = Paragraph::new(format!("Failed to load repository: {}", error))
+            .block(block)
+            .style(Style::default().fg(Color::Red));
+        frame.render_widget(failed, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5), // Summary
+            Constraint::Length(3), // Languages
+            Constraint::Min(6),    // Commits + traffic
+        ])
+        .split(inner);
+
+    render_summary(frame, chunks[0], state, detail);
+    render_languages(frame, chunks[1], detail);
+
+    match detail.traffic {
+        Some(ref traffic) => {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(chunks[2]);
+            render_commits(frame, columns[0], detail);
+            render_traffic(frame, columns[1], traffic);
+        }
+        None => render_commits(frame, chunks[2], detail),
+    }
+}
+
+/// Description, counters, license, latest release and topics
+fn render_summary(frame: &mut Frame, area: Rect, state: &AppState, detail: &RepoDetail) {
+    let repo = state
+        .github
+        .repos
+        .iter()
+        .find(|r| r.full_name == detail.full_name);
+    let width = area.width as usize;
+    let label = Style::default().fg(Color::DarkGray);
+
+    let description = repo
+        .and_then(|r| r.description.as_deref())
+        .unwrap_or("No description");
+    let mut counters = vec![
+        Span::styled("[*] ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format_count(repo.map_or(0, |r| r.stargazers_count) as u64),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw("  [Y] "),
+        Span::styled(
+            format_count(repo.map_or(0, |r| r.forks_count) as u64),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled("  PRs ", label),
+        Span::styled(
+            detail.open_prs.map_or("?".to_string(), |n| n.to_string()),
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled("  Issues ", label),
+        Span::styled(
+            detail.open_issues.to_string(),
+            Style::default().fg(Color::Green),
+        ),
+    ];
+    if let Some(ref license) = detail.license {
+        counters.push(Span::styled("  License ", label));
+        counters.push(Span::styled(
+            license.as_str(),
+            Style::default().fg(Color::White),
+        ));
+    }
+
+    let release = match detail.latest_release {
+        Some(ref release) => {
+            let mut spans = vec![
+                Span::styled("Latest release ", label),
+                Span::styled(
+                    release.tag.as_str(),
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if let Some(ref name) = release.name {
+                if *name != release.tag {
+                    spans.push(Span::raw(format!(" {}", name)));
+                }
+            }
+            if let Some(published) = release.published_at {
+                spans.push(Span::styled(format!(" ({})", format_age(published)), label));
+            }
+            Line::from(spans)
+        }
+        None => Line::from(Span::styled("No releases", label)),
+    };
+
+    let topics = if detail.topics.is_empty() {
+        Line::from("")
+    } else {
+        Line::from(vec![
+            Span::styled("Topics ", label),
+            Span::styled(
+                truncate_str(&detail.topics.join(", "), width.saturating_sub(7)),
+                Style::default().fg(Color::Cyan),
+            ),
+        ])
+    };
+
+    let lines = vec![
+        Line::from(Span::styled(
+            truncate_str(description, width),
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(counters),
+        release,
+        topics,
+    ];
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Proportional bar of language bytes with a legend underneath
+fn render_languages(frame: &mut Frame, area: Rect, detail: &RepoDetail) {
+    let shares = detail.language_shares();
+    if shares.is_empty() {
+        let none = Paragraph::new("No language data").style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(none, area);
+        return;
+    }
+
+    let bar = stacked_bar(
+        shares
+            .iter()
+            .map(|(name, pct)| (*pct, language_color(name))),
+        area.width as usize,
+    );
+
+    let mut legend = Vec::new();
+    for (name, pct) in &shares {
+        legend.push(Span::styled(
+            "■ ",
+            Style::default().fg(language_color(name)),
+        ));
+        legend.push(Span::raw(format!("{} {:.1}%  ", name, pct)));
+    }
+
+    frame.render_widget(Paragraph::new(vec![bar, Line::from(legend)]), area);
+}
+
+/// Recent commits on the default branch
+fn render_commits(frame: &mut Frame, area: Rect, detail: &RepoDetail) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(
+            " Recent Commits ",
+            Style::default().fg(Color::Green),
+        ));
+
+    if detail.commits.is_empty() {
+        let empty = Paragraph::new("No commits")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let width = block.inner(area).width as usize;
+    let lines: Vec<Line> = detail
+        .commits
+        .iter()
+        .map(|commit| {
+            let age = commit.date.map(format_age).unwrap_or_default();
+            let message_width =
+                width.saturating_sub(commit.sha.len() + commit.author.len() + age.len() + 3);
+            Line::from(vec![
+                Span::styled(commit.sha.as_str(), Style::default().fg(Color::Yellow)),
+                Span::raw(" "),
+                Span::styled(
+                    truncate_str(&commit.message, message_width),
+                    Style::default().fg(Color::White),
+                ),
+                Span::raw(" "),
+                Span::styled(commit.author.as_str(), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::styled(age, Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Daily views and clones over the last 14 days, on one date axis
+fn render_traffic(frame: &mut Frame, area: Rect, traffic: &RepoTraffic) {
+    let days = traffic.daily_counts();
+    let views: Vec<(f64, f64)> = days
+        .iter()
+        .enumerate()
+        .map(|(i, (_, views, _))| (i as f64, *views as f64))
+        .collect();
+    let clones: Vec<(f64, f64)> = days
+        .iter()
+        .enumerate()
+        .map(|(i, (_, _, clones))| (i as f64, *clones as f64))
+        .collect();
+    let total_views: u32 = traffic.views.iter().map(|d| d.count).sum();
+    let total_clones: u32 = traffic.clones.iter().map(|d| d.count).sum();
+
+    let max_y = views
+        .iter()
+        .chain(&clones)
+        .map(|(_, y)| *y)
+        .fold(1.0, f64::max);
+    let max_x = days.len().saturating_sub(1).max(1) as f64;
+    let day_label = |day: Option<&(chrono::NaiveDate, u32, u32)>| {
+        day.map(|(date, _, _)| date.format("%m-%d").to_string())
+            .unwrap_or_default()
+    };
+    let first_day = day_label(days.first());
+    let last_day = day_label(days.last());
+
+    let datasets = vec![
+        Dataset::default()
+            .name(format!("views {}", total_views))
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&views),
+        Dataset::default()
+            .name(format!("clones {}", total_clones))
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&clones),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray))
+                .title(Span::styled(
+                    " Traffic (14 days) ",
+                    Style::default().fg(Color::Green),
+                )),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, max_x])
+                .labels([first_day, last_day])
+                .style(Style::default().fg(Color::DarkGray)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, max_y])
+                .labels(["0".to_string(), format!("{}", max_y as u64)])
+                .style(Style::default().fg(Color::DarkGray)),
+        );
+    frame.render_widget(chart, area);
+}
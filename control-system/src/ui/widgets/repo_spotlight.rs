@@ -7,6 +7,8 @@ use ratatui::{
 };
 
 use crate::app::AppState;
+use crate::github::RepoQuery;
+use crate::ui::widgets::status_bar::mark_offline;
 
 /// Render the repository spotlight widget
 pub fn render_repo_spotlight(frame: &mut Frame, area: Rect, state: &AppState) {
@@ -19,6 +21,7 @@ pub fn render_repo_spotlight(frame: &mut Frame, area: Rect, state: &AppState) {
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         ));
+    let block = mark_offline(block, state, state.github.last_updated);
 
     if state.github.repos.is_empty() {
         let empty_text = if state.github.status.is_fetching() {
@@ -34,7 +37,11 @@ pub fn render_repo_spotlight(frame: &mut Frame, area: Rect, state: &AppState) {
         return;
     }
 
-    let top_repos = state.github.top_repos_by_stars(8);
+    let query = RepoQuery {
+        hide_forks: true,
+        ..Default::default()
+    };
+    let top_repos: Vec<_> = query.apply(&state.github.repos).into_iter().take(8).collect();
     
     let items: Vec<ListItem> = top_repos
         .iter()
@@ -88,7 +95,7 @@ pub fn render_repo_spotlight(frame: &mut Frame, area: Rect, state: &AppState) {
 }
 
 /// Get a color for a programming language
-fn language_color(lang: &str) -> Color {
+pub fn language_color(lang: &str) -> Color {
     match lang.to_lowercase().as_str() {
         "rust" => Color::Rgb(222, 165, 132),
         "python" => Color::Rgb(53, 114, 165),
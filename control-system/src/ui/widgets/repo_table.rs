@@ -0,0 +1,175 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::github::RepoColumn;
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::{format_count, format_delta, sparkline};
+use crate::util::time::format_relative;
+
+/// Render every repo of the selected account as a table that can be sorted
+/// by any column (`s`/`i`), filtered by text (`/`) and stripped of forks
+/// (`F`) or archived repos (`A`). Enter opens the highlighted repo.
+pub fn render_repo_table(frame: &mut Frame, area: Rect, state: &AppState) {
+    let query = &state.ui.repo_query;
+    let repos = state.listed_repos();
+    let total = state.github.account_repos(state.ui.selected_account).len();
+
+    let mut hidden = Vec::new();
+    if query.hide_forks {
+        hidden.push("forks");
+    }
+    if query.hide_archived {
+        hidden.push("archived");
+    }
+    let mut status = vec![Span::styled(
+        format!(" {}/{} by {} ", repos.len(), total, query.sort.label()),
+        Style::default().fg(Color::DarkGray),
+    )];
+    if state.ui.editing_repo_filter || !query.filter.is_empty() {
+        let cursor = if state.ui.editing_repo_filter {
+            "_"
+        } else {
+            ""
+        };
+        status.push(Span::styled(
+            format!("/{}{} ", query.filter, cursor),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if !hidden.is_empty() {
+        status.push(Span::styled(
+            format!("no {} ", hidden.join("/")),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(
+            " [*] Repositories ",
+            Style::default().fg(Color::Yellow),
+        ))
+        .title(Span::styled(
+            if state.github.repos_truncated(state.ui.selected_account) {
+                "(list cut short at the page limit) "
+            } else {
+                ""
+            },
+            Style::default().fg(Color::Red),
+        ))
+        .title(
+            Line::from(Span::styled(
+                format!(
+                    " {} ",
+                    sparkline(
+                        &state
+                            .github
+                            .history
+                            .daily_series(chrono::Utc::now(), 30, |s| s.stars)
+                    )
+                ),
+                Style::default().fg(Color::Yellow),
+            ))
+            .right_aligned(),
+        )
+        .title_bottom(Line::from(status));
+    let block = mark_offline(block, state, state.github.last_updated);
+
+    if repos.is_empty() {
+        let empty = Paragraph::new("No repositories match")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    // Column headers, with an arrow on the sorted one
+    let arrow = if query.descending { "v" } else { "^" };
+    let header_cell = |label: &'static str, column: RepoColumn| {
+        let style = Style::default().fg(Color::Cyan);
+        if query.sort == column {
+            Cell::from(format!("{}{}", label, arrow))
+                .style(style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED))
+        } else {
+            Cell::from(label).style(style)
+        }
+    };
+    let header = Row::new(vec![
+        header_cell("Name", RepoColumn::Name),
+        header_cell("Stars", RepoColumn::Stars),
+        Cell::from("7d").style(Style::default().fg(Color::Cyan)),
+        header_cell("Forks", RepoColumn::Forks),
+        header_cell("Issues", RepoColumn::Issues),
+        header_cell("Language", RepoColumn::Language),
+        header_cell("Pushed", RepoColumn::Pushed),
+    ]);
+
+    let week_ago = chrono::Utc::now() - chrono::Duration::days(7);
+    let rows: Vec<Row> = repos
+        .iter()
+        .map(|repo| {
+            let mut name = vec![Span::styled(
+                repo.name.as_str(),
+                Style::default().fg(Color::White),
+            )];
+            if repo.fork {
+                name.push(Span::styled(" fork", Style::default().fg(Color::DarkGray)));
+            }
+            if repo.archived {
+                name.push(Span::styled(
+                    " archived",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            // Stars gained this week, from the history store
+            let gained = match state.github.history.repo_delta(&repo.full_name, week_ago) {
+                Some(gained) if gained != 0 => Cell::from(format_delta(gained))
+                    .style(Style::default().fg(if gained > 0 { Color::Green } else { Color::Red })),
+                _ => Cell::from(""),
+            };
+            Row::new(vec![
+                Cell::from(Line::from(name)),
+                Cell::from(format_count(repo.stargazers_count as u64))
+                    .style(Style::default().fg(Color::Yellow)),
+                gained,
+                Cell::from(format_count(repo.forks_count as u64)),
+                Cell::from(repo.open_issues_count.to_string()),
+                Cell::from(repo.language.as_deref().unwrap_or("-"))
+                    .style(Style::default().fg(Color::DarkGray)),
+                Cell::from(
+                    repo.pushed_at
+                        .map(format_relative)
+                        .unwrap_or_else(|| "???".to_string()),
+                )
+                .style(Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(16),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Length(11),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(block)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    // The table scrolls to keep the highlighted row in view
+    let mut table_state = TableState::default().with_selected(Some(state.selected_repo_index()));
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
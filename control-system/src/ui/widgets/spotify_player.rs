@@ -8,8 +8,10 @@ use ratatui::{
 use ratatui_image::protocol::StatefulProtocol;
 use std::sync::Mutex;
 
+use super::album_art::{has_album_art, render_album_art};
 use crate::app::AppState;
-use crate::spotify::state::PlayerState;
+use crate::spotify::state::{PlayerState, SpotifyState};
+use crate::ui::widgets::status_bar::mark_offline;
 
 /// Area positions for clickable Spotify controls (set during render)
 pub struct SpotifyClickAreas {
@@ -70,6 +72,17 @@ pub enum SpotifyAction {
     Next,
 }
 
+/// Right-aligned block title showing the sleep timer or a fade in progress
+pub fn sleep_title(spotify: &SpotifyState) -> Option<Line<'static>> {
+    let text = if spotify.fading {
+        " fading out ".to_string()
+    } else {
+        let timer = spotify.sleep_timer.as_ref()?;
+        format!(" sleep {} ", timer.label(chrono::Utc::now()))
+    };
+    Some(Line::from(Span::styled(text, Style::default().fg(Color::Magenta))).right_aligned())
+}
+
 /// Render the Spotify player widget
 pub fn render_spotify_player(
     frame: &mut Frame,
@@ -77,7 +90,7 @@ pub fn render_spotify_player(
     state: &AppState,
     album_art_proto: &mut Option<StatefulProtocol>,
 ) {
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Green))
         .title(Span::styled(
@@ -86,6 +99,10 @@ pub fn render_spotify_player(
                 .fg(Color::Green)
                 .add_modifier(Modifier::BOLD),
         ));
+    block = mark_offline(block, state, state.spotify.player.last_updated);
+    if let Some(title) = sleep_title(&state.spotify) {
+        block = block.title(title);
+    }
 
     if !state.spotify.connected {
         let msg = if let Some(ref err) = state.spotify.error {
@@ -108,7 +125,7 @@ pub fn render_spotify_player(
     let player = &state.spotify.player;
 
     if player.track_name.is_empty() {
-        let paragraph = Paragraph::new("No track playing")
+        let paragraph = Paragraph::new(player.idle_message())
             .block(block)
             .style(Style::default().fg(Color::DarkGray));
         frame.render_widget(paragraph, area);
@@ -135,7 +152,7 @@ pub fn render_spotify_player(
         .split(inner);
 
     // Top row: album art (left) + track info + queue (right)
-    let has_art = album_art_proto.is_some();
+    let has_art = has_album_art(state, album_art_proto);
     let top_chunks = if has_art && main_rows[0].width > 30 {
         let art_width = (main_rows[0].height * 2).min(main_rows[0].width / 3).max(8);
         Layout::default()
@@ -150,17 +167,8 @@ pub fn render_spotify_player(
     };
 
     // LEFT: Album art with 1-cell padding
-    if let Some(proto) = album_art_proto.as_mut() {
-        let raw_art = top_chunks[0];
-        if raw_art.width > 2 && raw_art.height > 1 {
-            let padded = Rect {
-                x: raw_art.x + 1,
-                y: raw_art.y,
-                width: raw_art.width.saturating_sub(2),
-                height: raw_art.height.saturating_sub(1),
-            };
-            frame.render_stateful_widget(ratatui_image::StatefulImage::default(), padded, proto);
-        }
+    if has_art {
+        render_album_art(frame, top_chunks[0], state, album_art_proto);
     }
 
     // RIGHT: Track info (top) + queue (bottom)
@@ -195,8 +203,14 @@ pub fn render_spotify_player(
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         )),
-        // Blank line to give the title visual weight
-        Line::from(""),
+        // Blank line to give the title visual weight (podcast tag for episodes)
+        match player.episode_label() {
+            Some(label) => Line::from(Span::styled(
+                truncate_str(&label, max_w.saturating_sub(1)),
+                Style::default().fg(Color::Magenta),
+            )),
+            None => Line::from(""),
+        },
         // Album name - second biggest
         Line::from(Span::styled(
             truncate_str(&player.album_name, max_w.saturating_sub(1)),
@@ -260,7 +274,7 @@ fn render_progress_bar(frame: &mut Frame, area: Rect, player: &PlayerState) {
         return;
     }
 
-    let time_left = PlayerState::format_time(player.progress_ms);
+    let time_left = PlayerState::format_time(player.current_progress_ms());
     let time_right = PlayerState::format_time(player.duration_ms);
     let time_text_len = time_left.len() + time_right.len() + 2;
 
@@ -1,13 +1,16 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
+use chrono::{DateTime, Utc};
+
 use crate::app::AppState;
 use crate::github::FetchStatus;
+use crate::util::time::format_age;
 
 /// Render the status bar at the bottom
 pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
@@ -17,11 +20,15 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
 
     // Status message
     let status_msg = state.status_message();
-    let status_color = match &state.github.status {
-        FetchStatus::Fetching => Color::Yellow,
-        FetchStatus::Error(_) => Color::Red,
-        FetchStatus::Success => Color::Green,
-        FetchStatus::Idle => Color::DarkGray,
+    let status_color = if state.connectivity.online {
+        match &state.github.status {
+            FetchStatus::Fetching => Color::Yellow,
+            FetchStatus::Error(_) => Color::Red,
+            FetchStatus::Success => Color::Green,
+            FetchStatus::Idle => Color::DarkGray,
+        }
+    } else {
+        Color::Red
     };
 
     // Animation status indicator
@@ -52,11 +59,19 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
         Style::default().fg(rate_color),
     );
 
+    // Unread notifications
+    let unread = state.github.notifications.len();
+    let unread_indicator = Span::styled(
+        format!(" Notifications: {} unread ", unread),
+        Style::default().fg(if unread > 0 { Color::Yellow } else { Color::DarkGray }),
+    );
+
     // Page indicator
     let page_indicator = Span::styled(
         format!(
-            " [{}/4] {} ",
+            " [{}/{}] {} ",
             state.ui.current_page.index() + 1,
+            crate::app::Page::ALL.len(),
             state.ui.current_page.title()
         ),
         Style::default().fg(Color::Cyan),
@@ -68,18 +83,54 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
         Style::default().fg(Color::DarkGray),
     );
 
-    let line = Line::from(vec![
+    let mut spans = Vec::new();
+    if !state.connectivity.online {
+        spans.push(Span::styled(" OFFLINE ", offline_badge_style()));
+    }
+    spans.extend([
         anim_indicator,
         Span::raw("│"),
         Span::styled(format!(" {} ", status_msg), Style::default().fg(status_color)),
         Span::raw("│"),
         rate_indicator,
         Span::raw("│"),
-        page_indicator,
-        Span::raw("│"),
-        help_hint,
     ]);
+    // Notifications are only fetched with a token
+    if state.has_github_token {
+        spans.push(unread_indicator);
+        spans.push(Span::raw("│"));
+    }
+    spans.extend([page_indicator, Span::raw("│"), help_hint]);
+    let line = Line::from(spans);
 
     let paragraph = Paragraph::new(line).block(block);
     frame.render_widget(paragraph, area);
 }
+
+/// Style of the offline badge in the status bar and on widget borders
+fn offline_badge_style() -> Style {
+    Style::default()
+        .fg(Color::Black)
+        .bg(Color::Red)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// While offline, badge a widget's bottom border with the age of the data
+/// it shows (`updated` is when its source last fetched successfully)
+pub fn mark_offline<'a>(
+    block: Block<'a>,
+    state: &AppState,
+    updated: Option<DateTime<Utc>>,
+) -> Block<'a> {
+    if state.connectivity.online {
+        return block;
+    }
+    let age = match updated {
+        Some(updated) => format!(" {} old ", format_age(updated)),
+        None => " no data ".to_string(),
+    };
+    block.title_bottom(Line::from(vec![
+        Span::styled(" OFFLINE ", offline_badge_style()),
+        Span::styled(age, Style::default().fg(Color::Red)),
+    ]))
+}
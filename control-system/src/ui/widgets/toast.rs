@@ -0,0 +1,46 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::util::format::truncate_str;
+
+/// Toast box width, including borders
+const TOAST_WIDTH: u16 = 44;
+/// Toast box height: one line of text plus borders
+const TOAST_HEIGHT: u16 = 3;
+/// Rows left free at the top for the header tabs
+const TOP_OFFSET: u16 = 3;
+
+/// Render active toasts stacked in the top-right corner, newest on top
+pub fn render_toasts(frame: &mut Frame, area: Rect, state: &AppState) {
+    let width = TOAST_WIDTH.min(area.width);
+    let x = area.x + area.width - width;
+
+    for (i, toast) in state.toasts.iter().rev().enumerate() {
+        let y = area.y + TOP_OFFSET + i as u16 * TOAST_HEIGHT;
+        if y + TOAST_HEIGHT > area.y + area.height {
+            break;
+        }
+        let toast_area = Rect::new(x, y, width, TOAST_HEIGHT);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .style(Style::default().bg(Color::Black));
+        let text = Paragraph::new(Span::styled(
+            truncate_str(&toast.message, width.saturating_sub(2) as usize),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .block(block);
+
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(text, toast_area);
+    }
+}
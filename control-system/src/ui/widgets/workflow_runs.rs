@@ -0,0 +1,133 @@
+use chrono::Utc;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::github::{RunState, WorkflowRun};
+use crate::ui::widgets::status_bar::mark_offline;
+use crate::util::format::truncate_str;
+use crate::util::time::format_age;
+
+/// Badge text and colour for a run state
+fn state_badge(state: RunState) -> (&'static str, Color) {
+    match state {
+        RunState::Success => ("[ok]", Color::Green),
+        RunState::Failure => ("[x] ", Color::Red),
+        RunState::Running => ("[..]", Color::Yellow),
+        RunState::Queued => ("[--]", Color::Yellow),
+        RunState::Cancelled => ("[/] ", Color::DarkGray),
+        RunState::Skipped => ("[-] ", Color::DarkGray),
+    }
+}
+
+/// Compact duration, e.g. "45s", "4m30s", "1h05m"
+fn format_duration(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Render the GitHub Actions run board. Broken default-branch builds are
+/// listed first and turn the border red.
+pub fn render_workflow_runs(frame: &mut Frame, area: Rect, state: &AppState) {
+    let runs = state.github.workflow_board();
+    let broken = runs.iter().filter(|r| r.is_broken_default()).count();
+
+    let (border_color, title) = if broken > 0 {
+        (Color::Red, format!(" Actions: {} broken ", broken))
+    } else {
+        (Color::Cyan, " Actions ".to_string())
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(if broken > 0 {
+                    Color::Red
+                } else {
+                    Color::Yellow
+                })
+                .add_modifier(Modifier::BOLD),
+        ));
+    let block = mark_offline(block, state, state.github.last_updated);
+
+    if runs.is_empty() {
+        let msg = if !state.has_github_token {
+            "Set GITHUB_TOKEN to show workflow runs"
+        } else if state.github.status.is_fetching() {
+            "Loading workflow runs..."
+        } else {
+            "No workflow runs"
+        };
+        let empty = Paragraph::new(msg)
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let width = inner.width as usize;
+    let lines: Vec<Line> = runs
+        .into_iter()
+        .take(inner.height as usize)
+        .map(|run| run_line(run, width))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// One row: badge, repo, workflow, branch, duration, age
+fn run_line(run: &WorkflowRun, width: usize) -> Line<'static> {
+    let (badge, color) = state_badge(run.state);
+    let repo = run
+        .repo_name
+        .split('/')
+        .next_back()
+        .unwrap_or(&run.repo_name)
+        .to_string();
+    let duration = format_duration(run.duration(Utc::now()));
+    let age = format_age(run.updated_at);
+
+    // Workflow and branch share what's left after the fixed columns
+    let fixed = badge.len() + 1 + repo.len() + 1 + 1 + duration.len() + 1 + age.len();
+    let rest = width.saturating_sub(fixed);
+    let branch = truncate_str(&run.branch, (rest / 3).max(6));
+    let workflow = truncate_str(
+        &run.workflow_name,
+        rest.saturating_sub(branch.len() + 1).max(6),
+    );
+
+    let text_style = if run.state == RunState::Failure {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    Line::from(vec![
+        Span::styled(badge, Style::default().fg(color)),
+        Span::raw(" "),
+        Span::styled(repo, Style::default().fg(Color::Cyan)),
+        Span::raw(" "),
+        Span::styled(workflow, text_style),
+        Span::raw(" "),
+        Span::styled(branch, Style::default().fg(Color::Magenta)),
+        Span::raw(" "),
+        Span::styled(duration, Style::default().fg(Color::DarkGray)),
+        Span::raw(" "),
+        Span::styled(age, Style::default().fg(Color::DarkGray)),
+    ])
+}
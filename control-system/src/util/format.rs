@@ -43,6 +43,28 @@ pub fn truncate_str(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Render values as a block-character sparkline scaled between their min and max
+pub fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min).max(1);
+    values
+        .iter()
+        .map(|v| BARS[((v - min) * (BARS.len() as u64 - 1) / range) as usize])
+        .collect()
+}
+
+/// Format a signed change, e.g. "+3", "-1", "0"
+pub fn format_delta(delta: i64) -> String {
+    if delta > 0 {
+        format!("+{}", delta)
+    } else {
+        delta.to_string()
+    }
+}
+
 /// Format a percentage value
 pub fn format_percentage(value: f64) -> String {
     format!("{:.1}%", value)
@@ -75,4 +97,13 @@ mod tests {
         assert_eq!(truncate_str("hello world", 8), "hello...");
         assert_eq!(truncate_str("hi", 2), "hi");
     }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[1, 1, 1]), "▁▁▁");
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▄█");
+        assert_eq!(format_delta(3), "+3");
+        assert_eq!(format_delta(-2), "-2");
+    }
 }
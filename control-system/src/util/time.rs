@@ -78,6 +78,24 @@ pub fn format_datetime_compact(timestamp: DateTime<Utc>) -> String {
         .to_string()
 }
 
+/// Compact age of a timestamp (e.g., "5m", "3h", "2d", "6w")
+pub fn format_age(timestamp: DateTime<Utc>) -> String {
+    let duration = Utc::now().signed_duration_since(timestamp);
+    let minutes = duration.num_minutes().max(0);
+
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 60 * 24 {
+        format!("{}h", minutes / 60)
+    } else if duration.num_days() < 14 {
+        format!("{}d", duration.num_days())
+    } else if duration.num_days() < 365 {
+        format!("{}w", duration.num_days() / 7)
+    } else {
+        format!("{}y", duration.num_days() / 365)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +112,14 @@ mod tests {
         assert_eq!(format_relative(now - Duration::days(1)), "yesterday");
         assert_eq!(format_relative(now - Duration::days(3)), "3 days ago");
     }
+
+    #[test]
+    fn test_format_age() {
+        let now = Utc::now();
+
+        assert_eq!(format_age(now - Duration::minutes(5)), "5m");
+        assert_eq!(format_age(now - Duration::hours(3)), "3h");
+        assert_eq!(format_age(now - Duration::days(2)), "2d");
+        assert_eq!(format_age(now - Duration::days(30)), "4w");
+    }
 }